@@ -0,0 +1,216 @@
+use crate::github;
+use crate::printer::{self, Format};
+use core::fmt;
+use core::panic::Location;
+use std::cell::RefCell;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// The environment variable naming the directory artifacts are written to.
+pub(crate) const ARTIFACTS_VAR: &str = "PRETTY_ASSERTIONS_ARTIFACTS";
+
+/// The text diffed while rendering a failure message.
+#[derive(Default)]
+struct Capture {
+    left: String,
+    right: String,
+    // The number of diffs rendered, as some comparisons render one for each difference
+    diffs: usize,
+}
+
+/// The state of a failure message being rendered.
+struct Rendering {
+    format: Format,
+    capture: Capture,
+}
+
+thread_local! {
+    /// The failure message being rendered on this thread, if any.
+    static RENDERING: RefCell<Option<Rendering>> = const { RefCell::new(None) };
+}
+
+/// Marks a failure message as being rendered on this thread, for as long as it is alive.
+///
+/// The state is cleared when the guard is dropped, so that it does not outlive the
+/// message even if formatting a value in the message panics.
+struct RenderingGuard;
+
+impl RenderingGuard {
+    /// Start rendering a failure message, with diffs in the given format.
+    fn start(format: Format) -> RenderingGuard {
+        let rendering = Rendering {
+            format,
+            capture: Capture::default(),
+        };
+        RENDERING.with(|cell| *cell.borrow_mut() = Some(rendering));
+        RenderingGuard
+    }
+
+    /// Finish rendering, returning the text of the diffs rendered.
+    fn finish(self) -> Capture {
+        RENDERING
+            .with(|cell| cell.borrow_mut().take())
+            .map(|rendering| rendering.capture)
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for RenderingGuard {
+    fn drop(&mut self) {
+        RENDERING.with(|cell| {
+            if let Ok(mut rendering) = cell.try_borrow_mut() {
+                *rendering = None;
+            }
+        });
+    }
+}
+
+/// Record the text of a diff, if a failure message is being rendered on this thread.
+///
+/// Diffs rendered outside an assertion, such as by formatting a comparison directly, are
+/// not recorded.
+pub(crate) fn capture(left: &str, right: &str) {
+    RENDERING.with(|rendering| {
+        if let Some(rendering) = rendering.borrow_mut().as_mut() {
+            let capture = &mut rendering.capture;
+            if capture.diffs > 0 {
+                capture.left.push('\n');
+                capture.right.push('\n');
+            }
+            capture.left.push_str(left);
+            capture.right.push_str(right);
+            capture.diffs += 1;
+        }
+    });
+}
+
+/// The format of diffs, as selected for the failure message being rendered on this thread.
+///
+/// Outside an assertion, diffs are always in the default format.
+pub(crate) fn format() -> Format {
+    RENDERING.with(|rendering| {
+        rendering
+            .borrow()
+            .as_ref()
+            .map_or(Format::Pretty, |rendering| rendering.format)
+    })
+}
+
+/// The name of the directory for a failure, made from the test name and call site.
+///
+/// Test threads are named after the test they run. Characters which may not be valid in
+/// a file name are replaced with `_`.
+fn artifact_name(test: Option<&str>, location: &Location) -> String {
+    let name = format!(
+        "{}__{}_{}",
+        test.unwrap_or("unknown").replace("::", "__"),
+        location.file(),
+        location.line()
+    );
+    name.chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '_' | '-' | '.' => c,
+            _ => '_',
+        })
+        .collect()
+}
+
+/// Write the text of each side, and a unified diff between them, to a directory.
+fn write_artifacts(dir: &Path, capture: &Capture) -> std::io::Result<()> {
+    let mut patch = String::new();
+    printer::write_unified(&mut patch, &capture.left, &capture.right)
+        .expect("writing to a string cannot fail");
+    fs::create_dir_all(dir)?;
+    fs::write(dir.join("left.txt"), &capture.left)?;
+    fs::write(dir.join("right.txt"), &capture.right)?;
+    fs::write(dir.join("diff.patch"), patch)
+}
+
+/// Render the failure message of an assertion, as configured by the environment.
+///
+/// Diffs are presented in the format selected by `PRETTY_ASSERTIONS_FORMAT`. If a
+/// directory is configured and any diff is rendered in the message, the text of each side
+/// and a unified diff are written to a directory named after the test and call site, and
+/// the message is followed by the path of that directory.
+///
+/// Under GitHub Actions, an annotation of the call site is also written to stderr, with a
+/// summary of the failure. It is written to the stream directly, as output printed by a
+/// test is captured by the test harness, and only shown with the panic message.
+pub(crate) fn failure_message(message: fmt::Arguments, location: &Location) -> String {
+    let rendering = RenderingGuard::start(printer::selected_format());
+    let mut output = message.to_string();
+    let capture = rendering.finish();
+    let sides = match capture.diffs {
+        0 => None,
+        _ => Some((capture.left.as_str(), capture.right.as_str())),
+    };
+    if github::enabled() {
+        // A failure to write the annotation should not hide the failure itself
+        let _ =
+            std::io::stderr().write_all(github::annotation(location, &output, sides).as_bytes());
+    }
+    let root = match (std::env::var_os(ARTIFACTS_VAR), sides) {
+        (Some(root), Some(_)) => PathBuf::from(root),
+        _ => return output,
+    };
+
+    let dir = root.join(artifact_name(std::thread::current().name(), location));
+    match write_artifacts(&dir, &capture) {
+        Ok(()) => output.push_str(&format!(
+            "Wrote left.txt, right.txt and diff.patch to {}\n",
+            dir.display()
+        )),
+        Err(err) => output.push_str(&format!(
+            "Failed to write artifacts to {}: {}\n",
+            dir.display(),
+            err
+        )),
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn artifact_name_from_test_and_location() {
+        let location = Location::caller();
+        let name = artifact_name(Some("module::renders"), location);
+        assert!(name.starts_with("module__renders__"));
+        assert!(name.ends_with(&format!("src_artifacts.rs_{}", location.line())));
+        assert!(!name.contains('/'));
+    }
+
+    #[test]
+    fn capture_only_while_rendering() {
+        capture("ignored", "ignored");
+        assert!(format() == Format::Pretty);
+        let rendering = RenderingGuard::start(Format::Pretty);
+        capture("a", "b");
+        capture("c", "d");
+        let capture = rendering.finish();
+        assert_eq!(
+            (capture.left.as_str(), capture.right.as_str(), capture.diffs),
+            ("a\nc", "b\nd", 2)
+        );
+    }
+
+    #[test]
+    fn rendering_cleared_after_panic() {
+        struct Panics;
+
+        impl fmt::Display for Panics {
+            fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
+                panic!("formatting failed")
+            }
+        }
+
+        let result = std::panic::catch_unwind(|| {
+            failure_message(format_args!("{}", Panics), Location::caller())
+        });
+        assert!(result.is_err());
+        assert!(RENDERING.with(|cell| cell.borrow().is_none()));
+    }
+}
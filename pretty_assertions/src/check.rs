@@ -0,0 +1,152 @@
+#[cfg(feature = "alloc")]
+use alloc::string::{String, ToString};
+use core::fmt::{self, Debug, Display};
+
+/// Check that two values are equal to each other (using [`PartialEq`]), without panicking.
+///
+/// If the values are not equal, a [`Mismatch`] is returned, which displays as a pretty diff
+/// derived from the [`Debug`] representation of each value.
+///
+/// ```
+/// use pretty_assertions::check_eq;
+///
+/// assert!(check_eq(&3, &(1 + 2)).is_ok());
+///
+/// let mismatch = check_eq(&666, &999).unwrap_err();
+/// assert_eq!(mismatch.left(), "666");
+/// assert_eq!(mismatch.right(), "999");
+/// ```
+///
+/// The values may have different types, although in practice they are usually the same.
+pub fn check_eq<TLeft, TRight>(left: &TLeft, right: &TRight) -> Result<(), Mismatch>
+where
+    TLeft: PartialEq<TRight> + Debug + ?Sized,
+    TRight: Debug + ?Sized,
+{
+    if *left == *right {
+        Ok(())
+    } else {
+        Err(Mismatch::new(left, right))
+    }
+}
+
+/// Two values which were expected to be equal, but were not.
+///
+/// The [`Debug`] representation of each value is captured when the mismatch is created,
+/// so a `Mismatch` does not borrow from the values compared.
+///
+/// It displays as a pretty diff, in the same way as a [`Comparison`](crate::Comparison).
+/// This is also true of its `Debug` implementation, so that the diff is readable when a
+/// `Mismatch` is returned as an error from a test:
+///
+/// ```
+/// # #[cfg(feature = "std")] {
+/// use pretty_assertions::check_eq;
+/// use std::error::Error;
+///
+/// fn test() -> Result<(), Box<dyn Error>> {
+///     check_eq(&3, &(1 + 2))?;
+///     Ok(())
+/// }
+/// # test().unwrap();
+/// # }
+/// ```
+///
+/// # Features
+///
+/// [`std::error::Error`] is only implemented if the `std` feature is enabled.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    left: String,
+    right: String,
+    message: Option<String>,
+}
+
+impl Mismatch {
+    /// Capture the [`Debug`] representation of two values which are not equal.
+    pub fn new<TLeft, TRight>(left: &TLeft, right: &TRight) -> Mismatch
+    where
+        TLeft: Debug + ?Sized,
+        TRight: Debug + ?Sized,
+    {
+        Mismatch {
+            left: format!("{:#?}", left),
+            right: format!("{:#?}", right),
+            message: None,
+        }
+    }
+
+    /// Attach a custom message, which is displayed before the diff.
+    pub fn with_message<T: Display>(mut self, message: T) -> Mismatch {
+        self.message = Some(message.to_string());
+        self
+    }
+
+    /// The [`Debug`] representation of the left value.
+    pub fn left(&self) -> &str {
+        &self.left
+    }
+
+    /// The [`Debug`] representation of the right value.
+    pub fn right(&self) -> &str {
+        &self.right
+    }
+
+    /// The custom message, if one was attached.
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "check failed: `(left == right)`")?;
+        if let Some(message) = &self.message {
+            write!(f, ": {}", message)?;
+        }
+        writeln!(f)?;
+        writeln!(f)?;
+        // The debug strings have already been rendered, so diff them directly
+        let (left, right) = crate::compact::compact_sequences(&self.left, &self.right);
+        Display::fmt(&crate::StrComparison::new(&left, &right), f)
+    }
+}
+
+impl Debug for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Mismatch {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn check_eq_equal() {
+        assert_eq!(check_eq(&[1, 2], &[1, 2]), Ok(()));
+    }
+
+    #[test]
+    fn check_eq_not_equal() {
+        let mismatch = check_eq(&Some(1), &None).unwrap_err();
+        assert_eq!(mismatch.left(), "Some(\n    1,\n)");
+        assert_eq!(mismatch.right(), "None");
+        assert_eq!(mismatch.message(), None);
+    }
+
+    #[test]
+    fn mismatch_display() {
+        let mismatch = Mismatch::new(&666, &999).with_message("custom message");
+        let expected = "check failed: `(left == right)`: custom message\n\
+            \n\
+            \u{1b}[1mDiff\u{1b}[0m \u{1b}[31m< left\u{1b}[0m / \u{1b}[32mright >\u{1b}[0m :\n\
+            \u{1b}[31m<\u{1b}[0m\u{1b}[1;48;5;52;31m666\u{1b}[0m\n\
+            \u{1b}[32m>\u{1b}[0m\u{1b}[1;48;5;22;32m999\u{1b}[0m\n";
+        assert_eq!(format!("{}", mismatch), expected);
+        assert_eq!(format!("{:?}", mismatch), expected);
+    }
+}
@@ -0,0 +1,318 @@
+#[cfg(feature = "alloc")]
+use alloc::{format, string::String, vec::Vec};
+
+/// The number of scalar elements packed into each line.
+const ELEMENTS_PER_LINE: usize = 8;
+
+/// The indent added by `{:#?}` for each level of nesting.
+const INDENT: usize = 4;
+
+/// A scalar element of a sequence, in a line of `{:#?}` output.
+struct Element<'a> {
+    /// The `Debug` representation of the element.
+    text: &'a str,
+    /// The index of the element in its sequence.
+    index: usize,
+    /// The indent of the line.
+    indent: usize,
+    /// Whether the sequence has more elements than fit on one line.
+    long: bool,
+}
+
+/// For each line, the element it holds, if it is one of a sequence of scalar elements.
+fn elements<'a>(lines: &[&'a str]) -> Vec<Option<Element<'a>>> {
+    let mut elements: Vec<Option<Element>> = lines.iter().map(|_| None).collect();
+    for (open, line) in lines.iter().enumerate() {
+        if !line.ends_with('[') {
+            continue;
+        }
+        let indent = indent_of(line) + INDENT;
+        let count = lines[open + 1..]
+            .iter()
+            .take_while(|line| scalar_element(line, indent).is_some())
+            .count();
+        let closed = matches!(
+            lines.get(open + 1 + count),
+            Some(line) if is_close(line, indent - INDENT)
+        );
+        if !closed {
+            continue;
+        }
+        for index in 0..count {
+            let line = open + 1 + index;
+            elements[line] = scalar_element(lines[line], indent).map(|text| Element {
+                text,
+                index,
+                indent,
+                long: count > ELEMENTS_PER_LINE,
+            });
+        }
+    }
+    elements
+}
+
+/// Pack long sequences of scalar values in the `{:#?}` representations of two values onto
+/// fewer lines, so that they can be diffed.
+///
+/// Pretty debug output puts every element of a sequence on its own line, so a long
+/// `Vec<i32>` becomes hundreds of lines. Where every element of a sequence is a scalar
+/// (a number, `bool` or `char`), and there are more than fit on one line, the elements
+/// are packed several per line, each line annotated with the range of indices it holds:
+///
+/// ```text
+/// [
+///     [0..8]: 0, 1, 2, 3, 4, 5, 6, 7,
+///     [8..10]: 8, 9,
+/// ]
+/// ```
+///
+/// The values are diffed element by element first, and only runs of elements which are
+/// in both values are packed, so that inserting or removing an element does not change
+/// every line after it. Each element which is only in one value is put on its own line,
+/// annotated with its index. Where the elements of a line are at different indices in
+/// each value, it is annotated with both ranges, as in `[1..9 / 2..10]`.
+///
+/// Any other output is returned unchanged.
+pub(crate) fn compact_sequences(left: &str, right: &str) -> (String, String) {
+    let left_lines: Vec<&str> = left.split('\n').collect();
+    let right_lines: Vec<&str> = right.split('\n').collect();
+    let left_elements = elements(&left_lines);
+    let right_elements = elements(&right_lines);
+    let mut output: (Vec<String>, Vec<String>) = (Vec::new(), Vec::new());
+    let mut row: Vec<(&Element, &Element)> = Vec::new();
+
+    let (mut left_index, mut right_index) = (0, 0);
+    for change in ::diff::slice(&left_lines, &right_lines) {
+        match change {
+            ::diff::Result::Both(line, _) => {
+                match (&left_elements[left_index], &right_elements[right_index]) {
+                    (Some(left), Some(right))
+                        if left.indent == right.indent && (left.long || right.long) =>
+                    {
+                        let continues = matches!(
+                            row.last(),
+                            Some((last_left, last_right))
+                                if last_left.index + 1 == left.index
+                                    && last_right.index + 1 == right.index
+                        );
+                        if !continues || row.len() == ELEMENTS_PER_LINE {
+                            push_row(&mut output, &mut row);
+                        }
+                        row.push((left, right));
+                    }
+                    _ => {
+                        push_row(&mut output, &mut row);
+                        output.0.push((*line).into());
+                        output.1.push((*line).into());
+                    }
+                }
+                left_index += 1;
+                right_index += 1;
+            }
+            ::diff::Result::Left(line) => {
+                push_row(&mut output, &mut row);
+                output
+                    .0
+                    .push(changed_line(line, &left_elements[left_index]));
+                left_index += 1;
+            }
+            ::diff::Result::Right(line) => {
+                push_row(&mut output, &mut row);
+                output
+                    .1
+                    .push(changed_line(line, &right_elements[right_index]));
+                right_index += 1;
+            }
+        }
+    }
+    push_row(&mut output, &mut row);
+
+    (output.0.join("\n"), output.1.join("\n"))
+}
+
+/// Add a line of elements which are in both values to each output, if there are any.
+fn push_row(output: &mut (Vec<String>, Vec<String>), row: &mut Vec<(&Element, &Element)>) {
+    let ((first_left, first_right), (last_left, last_right)) = match (row.first(), row.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return,
+    };
+    let mut range = format!("{}..{}", first_left.index, last_left.index + 1);
+    if first_left.index != first_right.index {
+        range.push_str(&format!(
+            " / {}..{}",
+            first_right.index,
+            last_right.index + 1
+        ));
+    }
+    let texts: Vec<&str> = row.iter().map(|(left, _)| left.text).collect();
+    let line = format!(
+        "{:indent$}[{}]: {},",
+        "",
+        range,
+        texts.join(", "),
+        indent = first_left.indent,
+    );
+    output.0.push(line.clone());
+    output.1.push(line);
+    row.clear();
+}
+
+/// A line which is only in one value, annotated with its index if it is an element of a
+/// long sequence.
+fn changed_line(line: &str, element: &Option<Element>) -> String {
+    match element {
+        Some(element) if element.long => format!(
+            "{:indent$}[{}]: {},",
+            "",
+            element.index,
+            element.text,
+            indent = element.indent,
+        ),
+        _ => line.into(),
+    }
+}
+
+/// The number of leading spaces in a line.
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+/// Whether the line closes a sequence opened at the given indent.
+fn is_close(line: &str, indent: usize) -> bool {
+    indent_of(line) == indent && matches!(&line[indent..], "]" | "],")
+}
+
+/// If the line is a scalar element of a sequence at the given indent, return the element.
+fn scalar_element(line: &str, indent: usize) -> Option<&str> {
+    if indent_of(line) != indent {
+        return None;
+    }
+    let element = line[indent..].strip_suffix(',')?;
+    if is_scalar(element) {
+        Some(element)
+    } else {
+        None
+    }
+}
+
+/// Whether the `Debug` representation is that of a number, `bool` or `char`.
+fn is_scalar(value: &str) -> bool {
+    match value {
+        "true" | "false" | "NaN" | "inf" | "-inf" => return true,
+        _ => (),
+    }
+    if value.len() >= 3 && value.starts_with('\'') && value.ends_with('\'') {
+        // The contents of a char literal are a single, possibly escaped, character
+        return !value[1..value.len() - 1].contains('\'') || value == "'\\''";
+    }
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    digits.starts_with(|c: char| c.is_ascii_digit())
+        && digits
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-' | '+'))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn compact_sequences_short() {
+        let debug = "[\n    1,\n    2,\n]";
+        assert_eq!(compact_sequences(debug, debug).0, debug);
+    }
+
+    #[test]
+    fn compact_sequences_long() {
+        let debug = format!("{:#?}", (0..10).collect::<Vec<i32>>());
+        let expected = "[\n    [0..8]: 0, 1, 2, 3, 4, 5, 6, 7,\n    [8..10]: 8, 9,\n]";
+        assert_eq!(
+            compact_sequences(&debug, &debug),
+            (expected.into(), expected.into())
+        );
+    }
+
+    #[test]
+    fn compact_sequences_inserted() {
+        let left: Vec<i32> = (0..20).collect();
+        let mut right = left.clone();
+        right.insert(1, 99);
+        let (left, right) = compact_sequences(&format!("{:#?}", left), &format!("{:#?}", right));
+        let rows = "    [1..9 / 2..10]: 1, 2, 3, 4, 5, 6, 7, 8,
+    [9..17 / 10..18]: 9, 10, 11, 12, 13, 14, 15, 16,
+    [17..20 / 18..21]: 17, 18, 19,
+]";
+        assert_eq!(left, format!("[\n    [0..1]: 0,\n{}", rows));
+        assert_eq!(right, format!("[\n    [0..1]: 0,\n    [1]: 99,\n{}", rows));
+    }
+
+    #[test]
+    fn compact_sequences_changed() {
+        let left: Vec<i32> = (0..10).collect();
+        let mut right = left.clone();
+        right[8] = 99;
+        let (left, right) = compact_sequences(&format!("{:#?}", left), &format!("{:#?}", right));
+        let rows = "[\n    [0..8]: 0, 1, 2, 3, 4, 5, 6, 7,\n";
+        assert_eq!(left, format!("{}    [8]: 8,\n    [9..10]: 9,\n]", rows));
+        assert_eq!(right, format!("{}    [8]: 99,\n    [9..10]: 9,\n]", rows));
+    }
+
+    #[test]
+    fn compact_sequences_nested() {
+        #[derive(Debug)]
+        #[allow(dead_code)]
+        struct Foo {
+            values: Vec<f64>,
+            chars: Vec<char>,
+            names: Vec<&'static str>,
+        }
+        let debug = format!(
+            "{:#?}",
+            Foo {
+                values: vec![-1.5; 9],
+                chars: "abcdefghi'".chars().collect(),
+                names: vec!["a"; 9],
+            }
+        );
+        let expected = r#"Foo {
+    values: [
+        [0..8]: -1.5, -1.5, -1.5, -1.5, -1.5, -1.5, -1.5, -1.5,
+        [8..9]: -1.5,
+    ],
+    chars: [
+        [0..8]: 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h',
+        [8..10]: 'i', '\'',
+    ],
+    names: [
+        "a",
+        "a",
+        "a",
+        "a",
+        "a",
+        "a",
+        "a",
+        "a",
+        "a",
+    ],
+}"#;
+        assert_eq!(compact_sequences(&debug, &debug).0, expected);
+    }
+
+    #[test]
+    fn compact_sequences_mixed() {
+        let debug = format!("{:#?}", vec![Some(1); 9]);
+        assert_eq!(compact_sequences(&debug, &debug).0, debug);
+    }
+
+    #[test]
+    fn is_scalar_values() {
+        for value in &[
+            "0", "-12", "1.5", "1e-7", "-inf", "NaN", "true", "'a'", "'\\n'",
+        ] {
+            assert!(is_scalar(value), "{} should be scalar", value);
+        }
+        for value in &["\"a\"", "None", "Some(1)", "()", "-", "'a', 'b'"] {
+            assert!(!is_scalar(value), "{} should not be scalar", value);
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use crate::snapshot::{update_mode, write_file, UPDATE_VAR};
+use crate::StrComparison;
+use core::fmt::{self, Display};
+use core::ops::Range;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The lines of the expected text which differ from the actual text, counted from 1.
+///
+/// Lines which are only in the actual text are reported as the line they were expected
+/// before, which is one past the end of the file for lines added at the end.
+fn changed_lines(expected: &str, actual: &str) -> Vec<Range<usize>> {
+    let mut changed: Vec<Range<usize>> = Vec::new();
+    let mut line = 1;
+    for change in ::diff::lines(expected, actual) {
+        match (change, changed.last_mut()) {
+            (::diff::Result::Both(..), _) => line += 1,
+            (::diff::Result::Left(_), Some(range)) if range.end == line => {
+                range.end += 1;
+                line += 1;
+            }
+            (::diff::Result::Left(_), _) => {
+                changed.push(line..line + 1);
+                line += 1;
+            }
+            // Lines replacing those just removed are already reported
+            (::diff::Result::Right(_), Some(range)) if range.end >= line => (),
+            (::diff::Result::Right(_), _) => changed.push(line..line + 1),
+        }
+    }
+    changed
+}
+
+/// Compare a value with the contents of a file.
+///
+/// Line endings in the file are normalized, so that it matches when checked out with
+/// Windows line endings. In update mode, the file is written with the actual value if it differs, and the
+/// comparison always succeeds.
+fn check(path: &Path, display: &Path, actual: &str, update: bool) -> Result<(), FileMismatch> {
+    let expected = fs::read_to_string(path)
+        .ok()
+        .map(|text| text.replace("\r\n", "\n"));
+    if expected.as_deref() == Some(actual) {
+        return Ok(());
+    }
+    if update {
+        write_file(path, actual);
+        return Ok(());
+    }
+    Err(FileMismatch {
+        path: display.to_owned(),
+        expected,
+        actual: actual.to_owned(),
+    })
+}
+
+/// Compare a value with the contents of a file, relative to the manifest directory.
+///
+/// Used by the expansion of [`assert_file_eq!`](crate::assert_file_eq).
+pub fn assert_file<P: AsRef<Path>>(
+    manifest_dir: &str,
+    path: P,
+    actual: &str,
+) -> Result<(), FileMismatch> {
+    let path = path.as_ref();
+    check(
+        &Path::new(manifest_dir).join(path),
+        path,
+        actual,
+        update_mode(),
+    )
+}
+
+/// A value which did not match the contents of a file.
+///
+/// It displays as a diff from the file to the value, along with the lines of the file
+/// which differ.
+#[derive(Debug)]
+pub struct FileMismatch {
+    path: PathBuf,
+    expected: Option<String>,
+    actual: String,
+}
+
+impl Display for FileMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let path = self.path.display();
+        match &self.expected {
+            Some(expected) => {
+                write!(f, "File {} differs at line", path)?;
+                let changed = changed_lines(expected, &self.actual);
+                if changed.len() > 1 || changed.iter().any(|range| range.len() > 1) {
+                    write!(f, "s")?;
+                }
+                for (index, range) in changed.iter().enumerate() {
+                    let separator = if index == 0 { " " } else { ", " };
+                    match range.len() {
+                        1 => write!(f, "{}{}", separator, range.start)?,
+                        _ => write!(f, "{}{}-{}", separator, range.start, range.end - 1)?,
+                    }
+                }
+                writeln!(f, ".")?;
+            }
+            None => writeln!(f, "File {} does not exist.", path)?,
+        }
+        writeln!(f, "Set {}=1 to update it.", UPDATE_VAR)?;
+        writeln!(f)?;
+        let expected = self.expected.as_deref().unwrap_or_default();
+        Display::fmt(&StrComparison::new(expected, &self.actual), f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn changed_lines_ranges() {
+        assert_eq!(changed_lines("a\nb\nc\nd\n", "a\nB\nC\nd\n"), vec![2..4]);
+        assert_eq!(changed_lines("a\nb\nc\n", "x\nb\nc\nd\n"), vec![1..2, 4..5]);
+        assert!(changed_lines("a\nb", "a\nb").is_empty());
+    }
+
+    #[test]
+    fn check_update() {
+        let path = std::env::temp_dir().join(format!(
+            "pretty_assertions-check_update-{}.expected",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        let mismatch = check(&path, Path::new("a.expected"), "one\n", false).unwrap_err();
+        assert_eq!(mismatch.expected, None);
+
+        check(&path, Path::new("a.expected"), "one\n", true).unwrap();
+        let written = fs::read_to_string(&path);
+        let _ = fs::remove_file(&path);
+        assert_eq!(written.unwrap(), "one\n");
+    }
+
+    #[test]
+    fn check_line_endings() {
+        let path = std::env::temp_dir().join(format!(
+            "pretty_assertions-check_line_endings-{}.expected",
+            std::process::id()
+        ));
+        fs::write(&path, "one\r\ntwo\r\n").unwrap();
+        let result = check(&path, Path::new("a.expected"), "one\ntwo\n", false);
+        let _ = fs::remove_file(&path);
+        assert!(result.is_ok());
+    }
+}
@@ -0,0 +1,118 @@
+use crate::model::{Diff, LineKind};
+use core::panic::Location;
+
+/// The environment variable set to `true` by GitHub Actions.
+pub(crate) const GITHUB_ACTIONS_VAR: &str = "GITHUB_ACTIONS";
+
+/// The most changed lines listed in an annotation.
+const SUMMARY_LINES: usize = 10;
+
+/// Whether tests are running under GitHub Actions.
+pub(crate) fn enabled() -> bool {
+    std::env::var(GITHUB_ACTIONS_VAR).as_deref() == Ok("true")
+}
+
+/// Escape the message of a workflow command.
+fn escape_data(text: &str) -> String {
+    text.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape the value of a workflow command property.
+fn escape_property(text: &str) -> String {
+    escape_data(text).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// A short summary of a failure: the first line of its message, and the lines changed.
+///
+/// At most [`SUMMARY_LINES`] changed lines are listed, followed by the number omitted.
+fn summary(message: &str, diff: Option<&Diff>) -> String {
+    let mut summary = message.lines().next().unwrap_or_default().to_owned();
+    let diff = match diff {
+        Some(diff) => diff,
+        None => return summary,
+    };
+    let changed: Vec<_> = diff
+        .hunks
+        .iter()
+        .flat_map(|hunk| &hunk.lines)
+        .filter(|line| line.kind != LineKind::Equal)
+        .collect();
+    for line in changed.iter().take(SUMMARY_LINES) {
+        let sign = match line.kind {
+            LineKind::Delete => '-',
+            _ => '+',
+        };
+        summary.push_str(&format!("\n{}{}", sign, line.text));
+    }
+    if changed.len() > SUMMARY_LINES {
+        summary.push_str(&format!(
+            "\n... and {} more changed lines",
+            changed.len() - SUMMARY_LINES
+        ));
+    }
+    summary
+}
+
+/// The workflow command which annotates the location of a failed assertion with an error.
+///
+/// The text of the left and right sides is given if a diff was rendered in the message.
+pub(crate) fn annotation(
+    location: &Location,
+    message: &str,
+    sides: Option<(&str, &str)>,
+) -> String {
+    let diff = sides.map(|(left, right)| Diff::new(left, right));
+    format!(
+        "::error file={},line={},col={}::{}\n",
+        escape_property(location.file()),
+        location.line(),
+        location.column(),
+        escape_data(&summary(message, diff.as_ref()))
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn escaping() {
+        assert_eq!(escape_data("100%\r\nok: a,b"), "100%25%0D%0Aok: a,b");
+        assert_eq!(escape_property("C:\\a,b"), "C%3A\\a%2Cb");
+    }
+
+    #[test]
+    fn annotation_with_diff() {
+        let location = Location::caller();
+        let actual = annotation(
+            location,
+            "assertion failed: `(left == right)`\n\nDiff < left / right > :\n",
+            Some(("a\nb\nc", "a\nB\nc")),
+        );
+        assert_eq!(
+            actual,
+            format!(
+                "::error file={},line={},col={}::assertion failed: `(left == right)`%0A-b%0A+B\n",
+                location.file(),
+                location.line(),
+                location.column()
+            )
+        );
+    }
+
+    #[test]
+    fn summary_truncated() {
+        let left = "x\n".repeat(12);
+        let diff = Diff::new(&left, "");
+        let summary = summary("failed", Some(&diff));
+        assert!(summary.starts_with("failed\n-x\n"));
+        assert!(summary.ends_with("\n-x\n... and 2 more changed lines"));
+    }
+
+    #[test]
+    fn summary_without_diff() {
+        assert_eq!(summary("failed\n\ndetails", None), "failed");
+    }
+}
@@ -0,0 +1,107 @@
+use crate::markup::{text_node, Document, Element, Markup, MarkupComparison, Node};
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Display};
+
+/// A comparison of two HTML documents or fragments.
+///
+/// Each document is parsed and normalized before diffing: attributes are sorted by name,
+/// runs of whitespace in text are collapsed, and comments are dropped. Both sides are then
+/// pretty-printed with one element per line, and the lines diffed. The path of each
+/// element which differs, such as `/div/ul/li[2]`, is listed before the diff.
+///
+/// ```
+/// use pretty_assertions::HtmlComparison;
+///
+/// let left = r#"<ul class="menu" id="nav"><li>Home</li>  <li>About</li></ul>"#;
+/// let right = "<ul id=\"nav\" class=\"menu\">\n  <li>Home</li>\n  <li>Contact</li>\n</ul>";
+/// print!("{}", HtmlComparison::new(left, right));
+/// ```
+///
+/// If either document fails to parse, the error is reported and the text of each
+/// document is compared as a [`StrComparison`](crate::StrComparison) instead.
+///
+/// # Features
+///
+/// Requires the `tl` feature to be enabled.
+pub struct HtmlComparison<'a> {
+    inner: MarkupComparison<'a>,
+}
+
+impl<'a> HtmlComparison<'a> {
+    /// Parse two documents to be compared in future.
+    ///
+    /// Expensive diffing is deferred until calling `Display::fmt`.
+    pub fn new(left: &'a str, right: &'a str) -> HtmlComparison<'a> {
+        HtmlComparison {
+            inner: MarkupComparison {
+                markup: Markup::Html,
+                left: parse(left),
+                right: parse(right),
+            },
+        }
+    }
+
+    /// Whether the documents are the same once normalized.
+    ///
+    /// If either document fails to parse, the text of each is compared instead.
+    pub fn is_equal(&self) -> bool {
+        self.inner.is_equal()
+    }
+}
+
+impl Display for HtmlComparison<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.inner, f)
+    }
+}
+
+fn parse(text: &str) -> Document<'_> {
+    let nodes = match tl::parse(text, tl::ParserOptions::default()) {
+        Ok(dom) => Ok(convert(dom.parser(), dom.children())),
+        Err(err) => Err(format!("{:?}", err)),
+    };
+    Document { text, nodes }
+}
+
+fn convert(parser: &tl::Parser, handles: &[tl::NodeHandle]) -> Vec<Node> {
+    handles
+        .iter()
+        .filter_map(|handle| match handle.get(parser)? {
+            tl::Node::Tag(tag) => Some(Node::Element(Element::new(
+                // Names of elements and attributes are not case sensitive in HTML.
+                // The name of a self-closing tag, such as `<br/>`, includes the slash.
+                tag.name()
+                    .as_utf8_str()
+                    .trim_end_matches('/')
+                    .to_lowercase(),
+                tag.attributes()
+                    .iter()
+                    .map(|(name, value)| (name.to_lowercase(), value.map(String::from))),
+                convert(parser, tag.children().top().as_slice()),
+            ))),
+            tl::Node::Raw(text) => text_node(&text.as_utf8_str()),
+            tl::Node::Comment(_) => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn is_equal_ignores_formatting() {
+        let left = "<div id=a class=\"card\"><p>Hello\n  world</p><br></div>";
+        let right =
+            "<DIV class='card' id='a'>\n  <p>Hello world</p>\n  <br/>\n  <!-- note -->\n</DIV>";
+        assert!(HtmlComparison::new(left, right).is_equal());
+    }
+
+    #[test]
+    fn is_equal_attributes() {
+        let left = "<input type=\"checkbox\" checked>";
+        let right = "<input type=\"checkbox\">";
+        assert!(!HtmlComparison::new(left, right).is_equal());
+    }
+}
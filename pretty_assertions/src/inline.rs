@@ -0,0 +1,348 @@
+use crate::snapshot::update_mode;
+use core::ops::Range;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// A source file whose literals have been updated during this test run.
+///
+/// Literals are located in the original text of the file, as line numbers reported by
+/// the compiler refer to it. Every update is kept, and the file is rewritten from the
+/// original text each time, so that updates to several literals in one file combine.
+struct SourceFile {
+    path: PathBuf,
+    original: String,
+    patches: Vec<(Range<usize>, String)>,
+}
+
+impl SourceFile {
+    /// The text of the file with every update applied.
+    fn patched(&self) -> String {
+        let mut patches: Vec<&(Range<usize>, String)> = self.patches.iter().collect();
+        patches.sort_by_key(|(range, _)| range.start);
+        let mut output = String::with_capacity(self.original.len());
+        let mut end = 0;
+        for (range, literal) in patches {
+            output.push_str(&self.original[end..range.start]);
+            output.push_str(literal);
+            end = range.end;
+        }
+        output.push_str(&self.original[end..]);
+        output
+    }
+}
+
+/// The source files updated so far.
+static SOURCE_FILES: Mutex<Vec<SourceFile>> = Mutex::new(Vec::new());
+
+/// Format a string as a Rust string literal.
+///
+/// Text containing newlines, quotes or backslashes is written as a raw string, so that it
+/// reads the same as the value. A carriage return may not appear in a raw string, so text
+/// containing one is always escaped.
+fn literal(text: &str) -> String {
+    if !text.contains(['\n', '"', '\\']) || text.contains('\r') {
+        return format!("{:?}", text);
+    }
+    let mut hashes = String::new();
+    while text.contains(&format!("\"{}", hashes)) {
+        hashes.push('#');
+    }
+    format!("r{0}\"{1}\"{0}", hashes, text)
+}
+
+/// The value of a string literal, or `None` if it is not a valid string literal.
+///
+/// Line endings in the literal are normalized, as they are by the compiler.
+fn unescape(literal: &str) -> Option<String> {
+    let literal = literal.replace("\r\n", "\n");
+    if let Some(raw) = literal.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let terminator = format!("\"{}", "#".repeat(hashes));
+        return raw[hashes..]
+            .strip_prefix('"')?
+            .strip_suffix(&terminator)
+            .map(str::to_owned);
+    }
+    let body = literal.strip_prefix('"')?.strip_suffix('"')?;
+    let mut value = String::with_capacity(body.len());
+    let mut chars = body.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        let escaped = match chars.next()? {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            '0' => '\0',
+            c @ ('\\' | '\'' | '"') => c,
+            'x' => {
+                let digits: String = chars.by_ref().take(2).collect();
+                char::from(u8::from_str_radix(&digits, 16).ok().filter(u8::is_ascii)?)
+            }
+            'u' => {
+                if chars.next()? != '{' {
+                    return None;
+                }
+                let digits: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                char::from_u32(u32::from_str_radix(&digits.replace('_', ""), 16).ok()?)?
+            }
+            '\n' => {
+                // A line continuation, which also skips the indentation of the next line
+                while chars.next_if(|c| c.is_whitespace()).is_some() {}
+                continue;
+            }
+            _ => return None,
+        };
+        value.push(escaped);
+    }
+    Some(value)
+}
+
+/// Skip whitespace and comments, returning the offset of the next token.
+fn skip_trivia(source: &str, mut offset: usize) -> usize {
+    loop {
+        let rest = &source[offset..];
+        let trimmed = rest.trim_start();
+        offset += rest.len() - trimmed.len();
+        if trimmed.starts_with("//") {
+            offset += trimmed.find('\n').unwrap_or(trimmed.len());
+        } else if trimmed.starts_with("/*") {
+            offset += trimmed.find("*/").map_or(trimmed.len(), |end| end + 2);
+        } else {
+            return offset;
+        }
+    }
+}
+
+/// If a string literal starts at the offset, the offset of its end.
+fn string_end(source: &str, offset: usize) -> Option<usize> {
+    let rest = &source[offset..];
+    let unprefixed = rest.strip_prefix('b').unwrap_or(rest);
+    let prefix = rest.len() - unprefixed.len();
+    if let Some(raw) = unprefixed.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let body = raw[hashes..].strip_prefix('"')?;
+        let terminator = format!("\"{}", "#".repeat(hashes));
+        let end = body.find(&terminator)? + terminator.len();
+        return Some(source.len() - body.len() + end);
+    }
+    let body = unprefixed.strip_prefix('"')?;
+    let mut chars = body.char_indices();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '"' => return Some(offset + prefix + 1 + index + 1),
+            _ => (),
+        }
+    }
+    None
+}
+
+/// If a character literal starts at the offset, the offset of its end.
+///
+/// Returns `None` for a lifetime or label, which also starts with a quote.
+fn char_end(source: &str, offset: usize) -> Option<usize> {
+    let rest = source[offset..].strip_prefix('\'')?;
+    let mut chars = rest.chars();
+    let len = match chars.next()? {
+        '\\' => {
+            // Skip the escaped character, which may itself be a quote
+            let escaped = chars.next()?.len_utf8();
+            1 + escaped + rest[1 + escaped..].find('\'')?
+        }
+        c if chars.next()? == '\'' => c.len_utf8(),
+        _ => return None,
+    };
+    Some(offset + 1 + len + 1)
+}
+
+/// Find the string literal passed as the second argument of a macro invocation.
+///
+/// The invocation starts at the given line and column, both counted from 1 as reported
+/// by [`line!`] and [`column!`]. Returns `None` if the second argument is not a single
+/// string literal.
+fn find_literal(source: &str, line: u32, column: u32) -> Option<Range<usize>> {
+    let line_start: usize = source
+        .split_inclusive('\n')
+        .take(line.checked_sub(1)? as usize)
+        .map(str::len)
+        .sum();
+    let column = source[line_start..]
+        .char_indices()
+        .nth(column.checked_sub(1)? as usize)
+        .map_or(source.len(), |(index, _)| line_start + index);
+
+    // Find the delimiter opening the arguments, then the end of the first argument
+    let mut offset = column + source[column..].find(['(', '[', '{'])? + 1;
+    let mut depth = 0;
+    loop {
+        offset = skip_trivia(source, offset);
+        let c = source[offset..].chars().next()?;
+        if let Some(end) = string_end(source, offset).or_else(|| char_end(source, offset)) {
+            offset = end;
+            continue;
+        }
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' if depth == 0 => return None,
+            ')' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => break,
+            _ => (),
+        }
+        offset += c.len_utf8();
+    }
+
+    let start = skip_trivia(source, offset + 1);
+    let end = string_end(source, start)?;
+    let next = skip_trivia(source, end);
+    match source[next..].chars().next()? {
+        ',' | ')' | ']' | '}' => Some(start..end),
+        _ => None,
+    }
+}
+
+/// Replace the string literal in a macro invocation with the actual value.
+///
+/// Returns whether the literal was found and the file written.
+///
+/// # Panics
+///
+/// Panics, leaving the file as it is, if the literal found is not the expected value. This
+/// happens when the assertion is nested in another macro, as the location reported for it
+/// is that of the outermost invocation.
+fn update_file(path: &Path, line: u32, column: u32, expected: &str, actual: &str) -> bool {
+    let mut files = SOURCE_FILES.lock().unwrap_or_else(|err| err.into_inner());
+    let index = match files.iter().position(|file| file.path == path) {
+        Some(index) => index,
+        None => match fs::read_to_string(path) {
+            Ok(original) => {
+                files.push(SourceFile {
+                    path: path.to_owned(),
+                    original,
+                    patches: Vec::new(),
+                });
+                files.len() - 1
+            }
+            Err(_) => return false,
+        },
+    };
+    let file = &mut files[index];
+    let range = match find_literal(&file.original, line, column) {
+        Some(range) => range,
+        None => return false,
+    };
+    if unescape(&file.original[range.clone()]).as_deref() != Some(expected) {
+        panic!(
+            "failed to update the expected value at {}:{}:{}: found {}, which is not the \
+             expected value. Is the assertion nested in another macro?",
+            path.display(),
+            line,
+            column,
+            &file.original[range]
+        );
+    }
+    file.patches.retain(|(patched, _)| *patched != range);
+    file.patches.push((range, literal(actual)));
+    fs::write(&file.path, file.patched()).is_ok()
+}
+
+/// In update mode, replace the string literal expected by an assertion with the actual value.
+///
+/// The file is given as reported by [`file!`], which is relative to the workspace root, so
+/// it is looked up in the manifest directory and each of its parents. The literal is only
+/// updated if its value is the expected value. Returns whether the literal was updated, in
+/// which case the assertion should pass.
+pub fn update_literal(
+    manifest_dir: Option<&str>,
+    file: &str,
+    line: u32,
+    column: u32,
+    expected: &str,
+    actual: &str,
+) -> bool {
+    if !update_mode() {
+        return false;
+    }
+    let path = Path::new(manifest_dir.unwrap_or("."))
+        .ancestors()
+        .map(|dir| dir.join(file))
+        .find(|path| path.is_file());
+    match path {
+        Some(path) => update_file(&path, line, column, expected, actual),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn literal_plain_and_raw() {
+        assert_eq!(literal("foo\tbar"), r#""foo\tbar""#);
+        assert_eq!(literal("foo\nbar"), "r\"foo\nbar\"");
+        assert_eq!(literal(r#"say "hi""#), r###"r#"say "hi""#"###);
+        assert_eq!(literal("foo\r\nbar"), r#""foo\r\nbar""#);
+    }
+
+    #[test]
+    fn unescape_literals() {
+        for text in [
+            "foo\tbar",
+            "foo\nbar",
+            "say \"hi\"",
+            "a\r\nb\\",
+            "\u{1b}[1m",
+        ] {
+            assert_eq!(unescape(&literal(text)).as_deref(), Some(text));
+        }
+        assert_eq!(
+            unescape(
+                r#""\x41\u{2764}\0\
+                         b""#
+            )
+            .as_deref(),
+            Some("A\u{2764}\0b")
+        );
+        assert_eq!(unescape("r\"a\r\nb\"").as_deref(), Some("a\nb"));
+        assert_eq!(unescape(r#"b"bytes""#), None);
+        assert_eq!(unescape(r#""\q""#), None);
+    }
+
+    #[test]
+    fn find_literal_second_argument() {
+        let source = "fn f() {\n    assert_str_eq!(g(\"a, b\", ','), \"old\");\n}\n";
+        let range = find_literal(source, 2, 5).unwrap();
+        assert_eq!(&source[range], "\"old\"");
+
+        let source = "    ::pretty_assertions::assert_str_eq!(\n        x, // the value\n        r#\"a\"b\"#,\n        \"message\"\n    );";
+        let range = find_literal(source, 1, 5).unwrap();
+        assert_eq!(&source[range], "r#\"a\"b\"#");
+    }
+
+    #[test]
+    fn find_literal_not_literal() {
+        assert_eq!(find_literal("assert_str_eq!(a, b);", 1, 1), None);
+        assert_eq!(find_literal("assert_str_eq!(a, \"b\".trim());", 1, 1), None);
+        assert_eq!(find_literal("assert_str_eq!(a);", 1, 1), None);
+    }
+
+    #[test]
+    fn update_file_combines_updates() {
+        let path = std::env::temp_dir().join(format!(
+            "pretty_assertions-update_file-{}.rs",
+            std::process::id()
+        ));
+        fs::write(&path, "a!(x, \"one\");\na!(y, \"two\");\n").unwrap();
+        assert!(update_file(&path, 2, 1, "two", "2"));
+        assert!(update_file(&path, 1, 1, "one", "line\nbreak"));
+        let updated = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_file(&path);
+        assert_eq!(updated, "a!(x, r\"line\nbreak\");\na!(y, \"2\");\n");
+    }
+}
@@ -0,0 +1,166 @@
+use crate::Comparison;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::collections::VecDeque;
+use core::fmt::{self, Debug, Display};
+#[cfg(feature = "std")]
+use std::collections::VecDeque;
+
+/// The number of elements shown either side of the first difference.
+const CONTEXT: usize = 3;
+
+/// The most elements counted after those shown, so that an unbounded iterator can be
+/// compared with a bounded one.
+const LOOKAHEAD: usize = 1000;
+
+/// Compare two iterators element by element, without collecting them.
+///
+/// Only the elements around the first difference are kept, to be displayed on failure.
+/// After a difference is found, up to [`LOOKAHEAD`] more elements of each iterator are
+/// consumed to count its length.
+pub fn iter_eq<TLeft, TRight>(
+    left: TLeft,
+    right: TRight,
+) -> Result<(), IterMismatch<TLeft::Item, TRight::Item>>
+where
+    TLeft: IntoIterator,
+    TRight: IntoIterator,
+    TLeft::Item: PartialEq<TRight::Item>,
+{
+    let mut left = left.into_iter().fuse();
+    let mut right = right.into_iter().fuse();
+    let mut mismatch = IterMismatch {
+        index: 0,
+        left: VecDeque::with_capacity(CONTEXT * 2 + 1),
+        right: VecDeque::with_capacity(CONTEXT * 2 + 1),
+        left_len: Length::default(),
+        right_len: Length::default(),
+    };
+
+    loop {
+        match (left.next(), right.next()) {
+            (None, None) => return Ok(()),
+            (Some(left_item), Some(right_item)) if left_item == right_item => {
+                // Only keep the elements just before the first difference
+                if mismatch.left.len() == CONTEXT {
+                    mismatch.left.pop_front();
+                    mismatch.right.pop_front();
+                }
+                mismatch.left.push_back(left_item);
+                mismatch.right.push_back(right_item);
+                mismatch.index += 1;
+            }
+            (left_item, right_item) => {
+                mismatch.left.extend(left_item);
+                mismatch.right.extend(right_item);
+                break;
+            }
+        }
+    }
+
+    // Keep the elements just after the first difference, then count the remainder
+    let start = mismatch.window_start();
+    mismatch.left.extend(left.by_ref().take(CONTEXT));
+    mismatch.right.extend(right.by_ref().take(CONTEXT));
+    mismatch.left_len = Length::count(start + mismatch.left.len(), left);
+    mismatch.right_len = Length::count(start + mismatch.right.len(), right);
+
+    Err(mismatch)
+}
+
+/// The number of elements in an iterator, as far as it was counted.
+#[derive(Clone, Copy, Default, PartialEq)]
+struct Length {
+    count: usize,
+    // Whether the iterator ended, rather than being counted up to the lookahead
+    exact: bool,
+}
+
+impl Length {
+    /// Count up to [`LOOKAHEAD`] remaining elements of an iterator, after those already seen.
+    fn count<I: Iterator>(seen: usize, rest: I) -> Length {
+        let rest = rest.take(LOOKAHEAD + 1).count();
+        Length {
+            count: seen + rest,
+            exact: rest <= LOOKAHEAD,
+        }
+    }
+}
+
+impl Display for Length {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.exact {
+            write!(f, "at least ")?;
+        }
+        write!(f, "{}", self.count)
+    }
+}
+
+/// Two iterators which were expected to be equal, but were not.
+///
+/// Holds the elements around the first difference, and the length of each iterator, or a
+/// lower bound on it if the iterator was not consumed to the end.
+/// Displays as a diff of those elements, using [`Comparison`].
+pub struct IterMismatch<TLeft, TRight> {
+    // The index of the first difference
+    index: usize,
+    // The elements around the first difference
+    left: VecDeque<TLeft>,
+    right: VecDeque<TRight>,
+    // The number of elements in each iterator
+    left_len: Length,
+    right_len: Length,
+}
+
+impl<TLeft, TRight> IterMismatch<TLeft, TRight> {
+    /// The index of the first element shown.
+    fn window_start(&self) -> usize {
+        self.index - self.index.min(CONTEXT)
+    }
+}
+
+impl<TLeft, TRight> Display for IterMismatch<TLeft, TRight>
+where
+    TLeft: Debug,
+    TRight: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "First difference at index {}.", self.index)?;
+        if self.left_len != self.right_len {
+            write!(
+                f,
+                " Left has {} elements, right has {}.",
+                self.left_len, self.right_len
+            )?;
+        }
+        writeln!(f)?;
+        writeln!(f)?;
+        let start = self.window_start();
+        Display::fmt(
+            &Comparison::new(
+                &Window {
+                    start,
+                    items: &self.left,
+                },
+                &Window {
+                    start,
+                    items: &self.right,
+                },
+            ),
+            f,
+        )
+    }
+}
+
+/// A run of consecutive elements, which debug formats with the index of each element.
+struct Window<'a, T> {
+    start: usize,
+    items: &'a VecDeque<T>,
+}
+
+impl<T: Debug> Debug for Window<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map()
+            .entries((self.start..).zip(self.items.iter()))
+            .finish()
+    }
+}
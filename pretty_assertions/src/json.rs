@@ -0,0 +1,164 @@
+use crate::printer;
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Display};
+use serde_json::{Map, Value};
+
+/// A comparison of two JSON values.
+///
+/// In contrast to [`Comparison`](crate::Comparison), which uses the [`core::fmt::Debug`]
+/// representation, `JsonComparison` renders each value as pretty-printed JSON, with the
+/// keys of every object sorted. This gives the same output regardless of key order.
+///
+/// ```
+/// use pretty_assertions::JsonComparison;
+/// use serde_json::json;
+///
+/// let left = json!({"name": "Alice", "age": 42});
+/// let right = json!({"age": 43, "name": "Alice"});
+/// print!("{}", JsonComparison::new(&left, &right));
+/// ```
+///
+/// ## Ignoring extra fields
+///
+/// By convention, the left value is the actual value, and the right is the expected value.
+/// Using [`JsonComparison::ignore_extra_fields`], fields of an object on the left which are
+/// not present on the right are ignored, both when checking equality and in the diff.
+///
+/// # Features
+///
+/// Requires the `serde_json` feature to be enabled.
+pub struct JsonComparison<'a> {
+    left: &'a Value,
+    right: &'a Value,
+    ignore_extra_fields: bool,
+}
+
+impl<'a> JsonComparison<'a> {
+    /// Store two values to be compared in future.
+    ///
+    /// Expensive diffing is deferred until calling `Display::fmt`.
+    pub fn new(left: &'a Value, right: &'a Value) -> JsonComparison<'a> {
+        JsonComparison {
+            left,
+            right,
+            ignore_extra_fields: false,
+        }
+    }
+
+    /// Ignore fields of an object on the left which are not present on the right.
+    pub fn ignore_extra_fields(mut self) -> JsonComparison<'a> {
+        self.ignore_extra_fields = true;
+        self
+    }
+
+    /// Whether the values are equal, taking any ignored fields into account.
+    pub fn is_equal(&self) -> bool {
+        if self.ignore_extra_fields {
+            without_extra_fields(self.left, self.right) == *self.right
+        } else {
+            self.left == self.right
+        }
+    }
+}
+
+impl Display for JsonComparison<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let left = if self.ignore_extra_fields {
+            sorted(&without_extra_fields(self.left, self.right))
+        } else {
+            sorted(self.left)
+        };
+        let right = sorted(self.right);
+        // Serializing a `Value` cannot fail, as all keys are strings
+        let left = serde_json::to_string_pretty(&left).map_err(|_| fmt::Error)?;
+        let right = serde_json::to_string_pretty(&right).map_err(|_| fmt::Error)?;
+        printer::write_header(f)?;
+        printer::write_lines(f, &left, &right)
+    }
+}
+
+/// Rebuild the value with the keys of every object inserted in sorted order.
+///
+/// `serde_json` only sorts keys itself when the `preserve_order` feature is disabled,
+/// which another crate in the dependency graph may enable.
+fn sorted(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key.clone(), sorted(value)))
+                    .collect::<Map<String, Value>>(),
+            )
+        }
+        Value::Array(values) => Value::Array(values.iter().map(sorted).collect()),
+        value => value.clone(),
+    }
+}
+
+/// Remove the fields of objects in `actual` which are not present in `expected`.
+///
+/// Arrays are compared by index, and any extra elements in `actual` are kept.
+fn without_extra_fields(actual: &Value, expected: &Value) -> Value {
+    match (actual, expected) {
+        (Value::Object(actual), Value::Object(expected)) => Value::Object(
+            actual
+                .iter()
+                .filter_map(|(key, value)| {
+                    let expected = expected.get(key)?;
+                    Some((key.clone(), without_extra_fields(value, expected)))
+                })
+                .collect(),
+        ),
+        (Value::Array(actual), Value::Array(expected)) => Value::Array(
+            actual
+                .iter()
+                .enumerate()
+                .map(|(index, value)| match expected.get(index) {
+                    Some(expected) => without_extra_fields(value, expected),
+                    None => value.clone(),
+                })
+                .collect(),
+        ),
+        (actual, _) => actual.clone(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn sorted_nested() {
+        let value = json!({"b": [{"d": 1, "c": 2}], "a": null});
+        let expected = "{\"a\":null,\"b\":[{\"c\":2,\"d\":1}]}";
+        assert_eq!(serde_json::to_string(&sorted(&value)).unwrap(), expected);
+    }
+
+    #[test]
+    fn without_extra_fields_nested() {
+        let actual = json!({"id": 1, "items": [{"id": 2, "name": "a"}, {"id": 3}], "x": {}});
+        let expected = json!({"items": [{"name": "a"}], "x": 1});
+        assert_eq!(
+            without_extra_fields(&actual, &expected),
+            json!({"items": [{"name": "a"}, {"id": 3}], "x": {}}),
+        );
+    }
+
+    #[test]
+    fn is_equal_ignore_extra_fields() {
+        let actual = json!({"id": 1, "name": "Alice"});
+        let expected = json!({"name": "Alice"});
+        assert!(!JsonComparison::new(&actual, &expected).is_equal());
+        assert!(JsonComparison::new(&actual, &expected)
+            .ignore_extra_fields()
+            .is_equal());
+        assert!(!JsonComparison::new(&expected, &actual)
+            .ignore_extra_fields()
+            .is_equal());
+    }
+}
@@ -63,6 +63,30 @@
 //! * `assert_ne` is also switched to multi-line presentation, but does _not_ show
 //!   a diff.
 //!
+//! ## Environment variables
+//!
+//! Some behaviour can be changed when running tests, using these environment variables.
+//! They require the `std` feature.
+//!
+//! - `PRETTY_ASSERTIONS_UPDATE=1`: update expected values instead of failing. This applies
+//!   to [`assert_snapshot!`], [`assert_file_eq!`], and string literals expected by
+//!   [`assert_str_eq!`].
+//! - `PRETTY_ASSERTIONS_ARTIFACTS=<dir>`: when an assertion fails, also write the text of
+//!   each side to `left.txt` and `right.txt`, and a unified diff to `diff.patch`. The files
+//!   are written to a subdirectory of `<dir>`, named after the test and the location of the
+//!   assertion, which is given at the end of the panic message. This is useful in CI,
+//!   where long diffs may be truncated in logs.
+//! - `PRETTY_ASSERTIONS_FORMAT=<format>`: choose how diffs are presented. The default is
+//!   `pretty`, the colorised format shown above. With `unified`, a plain unified diff is
+//!   shown instead, in the format of `diff -u`, which can be read by tools such as `patch`.
+//!   With `markdown`, the unified diff is shown in a fenced Markdown code block, ready to
+//!   paste into a pull request comment, and diffs of more than 50 lines are collapsed in a
+//!   `<details>` section. With `json`, the [`Diff`] is shown as JSON, which requires the
+//!   `serde_json` feature.
+//! - `GITHUB_ACTIONS=true`: set by GitHub Actions. When an assertion fails, an `::error`
+//!   workflow command is also written to stderr, which annotates the line of the assertion
+//!   with the first line of the message and the lines which changed.
+//!
 //! ## Features
 //!
 //! Features provided by the crate are:
@@ -73,6 +97,20 @@
 //!   Exactly one of `std` and `alloc` is required.
 //! - `unstable`: opt-in to unstable features that may not follow Semantic Versioning.
 //!   The implementation behind this feature is subject to change without warning between patch versions.
+//! - `regex`: enable [`assert_matches_regex!`] and [`RegexComparison`], for matching strings
+//!   against regular expressions, and [`Normalizer`], for hiding noise in diffs.
+//! - `serde_json`: enable [`assert_json_eq!`], [`assert_json_include!`] and [`JsonComparison`],
+//!   for comparing JSON values. Also enables `serde`.
+//! - `serde`: enable [`assert_serde_eq!`] and [`SerdeComparison`], for comparing any values
+//!   which implement `Serialize`, and implement `Serialize` for [`Diff`].
+//! - `serde_yaml`: enable [`assert_yaml_eq!`] and [`YamlComparison`], for comparing YAML
+//!   documents regardless of formatting.
+//! - `toml`: enable [`assert_toml_eq!`] and [`TomlComparison`], for comparing TOML
+//!   documents regardless of formatting.
+//! - `tl`: enable [`assert_html_eq!`] and [`HtmlComparison`], for comparing HTML documents
+//!   regardless of attribute order and whitespace.
+//! - `roxmltree`: enable [`assert_xml_eq!`] and [`XmlComparison`], for comparing XML
+//!   documents regardless of attribute order and whitespace.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![deny(clippy::all, missing_docs, unsafe_code)]
@@ -80,9 +118,80 @@
 #[cfg(feature = "alloc")]
 #[macro_use]
 extern crate alloc;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
 use core::fmt::{self, Debug, Display};
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+use text::TextOptions;
 
+#[cfg(feature = "std")]
+mod artifacts;
+mod check;
+mod compact;
+#[cfg(feature = "std")]
+mod file;
+#[cfg(feature = "std")]
+mod github;
+#[cfg(feature = "tl")]
+mod html;
+#[cfg(feature = "std")]
+mod inline;
+mod iter;
+#[cfg(feature = "serde_json")]
+mod json;
+#[cfg(any(feature = "tl", feature = "roxmltree"))]
+mod markup;
+mod model;
+#[cfg(feature = "regex")]
+mod normalize;
 mod printer;
+mod redact;
+#[cfg(feature = "regex")]
+mod regex;
+mod render;
+#[cfg(feature = "serde")]
+mod serde;
+#[cfg(feature = "std")]
+mod snapshot;
+mod soft;
+#[cfg(any(feature = "serde_yaml", feature = "toml"))]
+mod structured;
+mod text;
+#[cfg(feature = "toml")]
+mod toml;
+#[cfg(feature = "roxmltree")]
+mod xml;
+#[cfg(feature = "serde_yaml")]
+mod yaml;
+
+pub use crate::check::{check_eq, Mismatch};
+#[cfg(feature = "tl")]
+pub use crate::html::HtmlComparison;
+#[cfg(feature = "serde_json")]
+pub use crate::json::JsonComparison;
+pub use crate::model::{Diff, Hunk, Line, LineKind, Span};
+#[cfg(feature = "regex")]
+pub use crate::normalize::Normalizer;
+#[cfg(all(feature = "regex", feature = "std"))]
+pub use crate::normalize::{add_global_normalizer, clear_global_normalizers};
+pub use crate::redact::Redaction;
+#[cfg(feature = "regex")]
+pub use crate::regex::RegexComparison;
+pub use crate::render::{
+    HtmlRenderer, MarkdownRenderer, PrettyRenderer, Renderer, UnifiedRenderer,
+};
+#[cfg(feature = "serde")]
+pub use crate::serde::SerdeComparison;
+pub use crate::soft::SoftAssertions;
+#[cfg(feature = "toml")]
+pub use crate::toml::TomlComparison;
+#[cfg(feature = "roxmltree")]
+pub use crate::xml::XmlComparison;
+#[cfg(feature = "serde_yaml")]
+pub use crate::yaml::YamlComparison;
 
 /// A comparison of two values.
 ///
@@ -95,6 +204,37 @@ mod printer;
 /// ```
 ///
 /// The values may have different types, although in practice they are usually the same.
+///
+/// ## Long sequences
+///
+/// Sequences of more than a few numbers, `bool`s or `char`s are packed several
+/// elements per line, with each line annotated by the range of indices it holds:
+///
+/// ```text
+/// [
+///     [0..8]: 0, 1, 2, 3, 4, 5, 6, 7,
+///     [8..10]: 8, 9,
+/// ]
+/// ```
+///
+/// Only elements which are in both values are packed together. Each element which was
+/// inserted, removed or changed is shown on its own line, annotated with its index, so
+/// the lines after it still line up:
+///
+/// ```text
+///  [
+///      [0..1]: 0,
+/// >    [1]: 99,
+///      [1..9 / 2..10]: 1, 2, 3, 4, 5, 6, 7, 8,
+///      [9..10 / 10..11]: 9,
+///  ]
+/// ```
+///
+/// ## Redaction
+///
+/// Parts of each value which are expected to differ, such as timestamps or IDs, can be
+/// replaced with `[redacted]` using [`Comparison::redact`]. See [`Redaction`] for the
+/// rules available, and [`assert_eq_redacted!`] for an assertion which uses them.
 pub struct Comparison<'a, TLeft, TRight>
 where
     TLeft: ?Sized,
@@ -102,6 +242,9 @@ where
 {
     left: &'a TLeft,
     right: &'a TRight,
+    redactions: Vec<Redaction>,
+    #[cfg(feature = "regex")]
+    normalizers: Vec<Normalizer>,
 }
 
 impl<'a, TLeft, TRight> Comparison<'a, TLeft, TRight>
@@ -113,7 +256,120 @@ where
     ///
     /// Expensive diffing is deferred until calling `Debug::fmt`.
     pub fn new(left: &'a TLeft, right: &'a TRight) -> Comparison<'a, TLeft, TRight> {
-        Comparison { left, right }
+        Comparison {
+            left,
+            right,
+            redactions: Vec::new(),
+            #[cfg(feature = "regex")]
+            normalizers: Vec::new(),
+        }
+    }
+
+    /// Replace part of each value with `[redacted]`, before diffing.
+    ///
+    /// Accepts a [`Redaction`], or anything which converts into one, such as a field
+    /// name or a path like `".user.created_at"`.
+    pub fn redact<T: Into<Redaction>>(mut self, redaction: T) -> Comparison<'a, TLeft, TRight> {
+        self.redactions.push(redaction.into());
+        self
+    }
+
+    /// Rewrite the debug representation of each value with a [`Normalizer`], before diffing.
+    ///
+    /// This only affects the rendered diff, not [`Comparison::is_equal`].
+    ///
+    /// # Features
+    ///
+    /// Requires the `regex` feature to be enabled.
+    #[cfg(feature = "regex")]
+    pub fn normalize(mut self, normalizer: Normalizer) -> Comparison<'a, TLeft, TRight> {
+        self.normalizers.push(normalizer);
+        self
+    }
+}
+
+impl<TLeft, TRight> Comparison<'_, TLeft, TRight>
+where
+    TLeft: Debug + ?Sized,
+    TRight: Debug + ?Sized,
+{
+    /// Whether the values have the same [`Debug`] representation, once redacted.
+    ///
+    /// This is used in place of [`PartialEq`] where redactions are applied, as the
+    /// redacted parts of each value are not compared.
+    pub fn is_equal(&self) -> bool {
+        self.debug_string(false) == self.debug_string(true)
+    }
+
+    /// Render the diff as a plain unified diff, in the format of `diff -u`.
+    ///
+    /// The diff is of the same text shown by the [`Display`] implementation, with each side
+    /// named `left` and `right`. It is empty if there are no differences to show.
+    ///
+    /// ```
+    /// use pretty_assertions::Comparison;
+    ///
+    /// let diff = Comparison::new(&[1, 2], &[1, 3]).unified_diff();
+    /// assert!(diff.starts_with("--- left\n+++ right\n@@ -1,4 +1,4 @@\n [\n     1,\n-    2,\n+    3,\n"));
+    /// ```
+    pub fn unified_diff(&self) -> String {
+        let (left, right) = self.diff_text();
+        let mut output = String::new();
+        printer::write_unified(&mut output, &left, &right)
+            .expect("writing to a string cannot fail");
+        output
+    }
+
+    /// Compute the diff as data, for use by other tools.
+    ///
+    /// The diff is of the same text shown by the [`Display`] implementation.
+    pub fn diff(&self) -> Diff {
+        let (left, right) = self.diff_text();
+        Diff::new(&left, &right)
+    }
+
+    /// Render the diff as self-contained HTML, for embedding in test reports.
+    ///
+    /// The diff is of the same text shown by the [`Display`] implementation. Each line is
+    /// a row of a table, and where a single line is replaced, the characters which differ
+    /// are marked with `<del>` and `<ins>`. The stylesheet is included, scoped to the
+    /// `pretty-assertions-diff` class of the containing `<div>`.
+    ///
+    /// ```
+    /// use pretty_assertions::Comparison;
+    ///
+    /// let html = Comparison::new(&[1, 2], &[1, 3]).html_diff();
+    /// assert!(html.contains("<td>    <del>2</del>,</td>"));
+    /// assert!(html.contains("<td>    <ins>3</ins>,</td>"));
+    /// ```
+    pub fn html_diff(&self) -> String {
+        let (left, right) = self.diff_text();
+        let mut output = String::new();
+        printer::write_html(&mut output, &left, &right).expect("writing to a string cannot fail");
+        output
+    }
+
+    /// The redacted [`Debug`] representation of the left or right value.
+    fn debug_string(&self, right: bool) -> String {
+        let debug = if right {
+            format!("{:#?}", self.right)
+        } else {
+            format!("{:#?}", self.left)
+        };
+        redact::redact(&debug, &self.redactions)
+    }
+
+    /// The text of each value which is diffed: redacted, normalized and compacted.
+    fn diff_text(&self) -> (String, String) {
+        // To diff arbitary types, render them as debug strings
+        let left_debug = self.debug_string(false);
+        let right_debug = self.debug_string(true);
+        #[cfg(feature = "regex")]
+        let (left_debug, right_debug) = (
+            normalize::normalize(&left_debug, &self.normalizers),
+            normalize::normalize(&right_debug, &self.normalizers),
+        );
+        compact::compact_sequences(&left_debug, &right_debug)
     }
 }
 
@@ -123,9 +379,7 @@ where
     TRight: Debug + ?Sized,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        // To diff arbitary types, render them as debug strings
-        let left_debug = format!("{:#?}", self.left);
-        let right_debug = format!("{:#?}", self.right);
+        let (left_debug, right_debug) = self.diff_text();
         // And then diff the debug output
         printer::write_header(f)?;
         printer::write_lines(f, &left_debug, &right_debug)
@@ -169,6 +423,23 @@ where
 /// ```
 ///
 /// The values may have different types, although in practice they are usually the same.
+///
+/// ## Ignoring differences
+///
+/// Some differences can be ignored, using [`StrComparison::ignore_case`],
+/// [`StrComparison::collapse_whitespace`], [`StrComparison::trim_lines`] and
+/// [`StrComparison::ignore_blank_lines`]. Lines which only differ in these ways are shown
+/// as unchanged, and [`StrComparison::is_equal`] takes them into account. The diff still
+/// shows each line as it appears in the original text.
+///
+/// ```
+/// use pretty_assertions::StrComparison;
+///
+/// let comparison = StrComparison::new("SELECT id\n  FROM users", "select id\nfrom users")
+///     .ignore_case()
+///     .trim_lines();
+/// assert!(comparison.is_equal());
+/// ```
 pub struct StrComparison<'a, TLeft, TRight>
 where
     TLeft: ?Sized,
@@ -176,6 +447,9 @@ where
 {
     left: &'a TLeft,
     right: &'a TRight,
+    options: TextOptions,
+    #[cfg(feature = "regex")]
+    normalizers: Vec<Normalizer>,
 }
 
 impl<'a, TLeft, TRight> StrComparison<'a, TLeft, TRight>
@@ -187,7 +461,116 @@ where
     ///
     /// Expensive diffing is deferred until calling `Debug::fmt`.
     pub fn new(left: &'a TLeft, right: &'a TRight) -> StrComparison<'a, TLeft, TRight> {
-        StrComparison { left, right }
+        StrComparison {
+            left,
+            right,
+            options: TextOptions::default(),
+            #[cfg(feature = "regex")]
+            normalizers: Vec::new(),
+        }
+    }
+
+    /// Ignore differences in the case of letters.
+    pub fn ignore_case(mut self) -> StrComparison<'a, TLeft, TRight> {
+        self.options.ignore_case = true;
+        self
+    }
+
+    /// Treat each run of whitespace within a line as a single space.
+    pub fn collapse_whitespace(mut self) -> StrComparison<'a, TLeft, TRight> {
+        self.options.collapse_whitespace = true;
+        self
+    }
+
+    /// Ignore whitespace at the start and end of each line.
+    pub fn trim_lines(mut self) -> StrComparison<'a, TLeft, TRight> {
+        self.options.trim_lines = true;
+        self
+    }
+
+    /// Ignore lines which are empty, or only contain whitespace.
+    pub fn ignore_blank_lines(mut self) -> StrComparison<'a, TLeft, TRight> {
+        self.options.ignore_blank_lines = true;
+        self
+    }
+
+    /// Whether the strings are equal, ignoring any differences allowed by the options set.
+    pub fn is_equal(&self) -> bool {
+        self.options.eq(self.left.as_ref(), self.right.as_ref())
+    }
+
+    /// Render the diff as a plain unified diff, in the format of `diff -u`.
+    ///
+    /// Each side is named `left` and `right`, so the diff can be applied with `patch` to a
+    /// file containing the left string. It is empty if there are no differences to show.
+    ///
+    /// ```
+    /// use pretty_assertions::StrComparison;
+    ///
+    /// let diff = StrComparison::new("foo\nbar\n", "foo\nbaz\n").unified_diff();
+    /// assert_eq!(diff, "--- left\n+++ right\n@@ -1,2 +1,2 @@\n foo\n-bar\n+baz\n");
+    /// ```
+    pub fn unified_diff(&self) -> String {
+        let (left, right) = self.diff_text();
+        let mut output = String::new();
+        self.options
+            .write_unified(&mut output, &left, &right)
+            .expect("writing to a string cannot fail");
+        output
+    }
+
+    /// Compute the diff as data, for use by other tools.
+    ///
+    /// Lines which only differ in ways ignored by the options set are unchanged.
+    pub fn diff(&self) -> Diff {
+        let (left, right) = self.diff_text();
+        self.options.diff(&left, &right)
+    }
+
+    /// Render the diff as self-contained HTML, for embedding in test reports.
+    ///
+    /// Each line is a row of a table, and where a single line is replaced, the characters
+    /// which differ are marked with `<del>` and `<ins>`. The stylesheet is included, scoped
+    /// to the `pretty-assertions-diff` class of the containing `<div>`.
+    ///
+    /// ```
+    /// use pretty_assertions::StrComparison;
+    ///
+    /// let html = StrComparison::new("foo\n<bar>\n", "foo\n<baz>\n").html_diff();
+    /// assert!(html.contains("<td>&lt;ba<del>r</del>&gt;</td>"));
+    /// assert!(html.contains("<td>&lt;ba<ins>z</ins>&gt;</td>"));
+    /// ```
+    pub fn html_diff(&self) -> String {
+        let (left, right) = self.diff_text();
+        let mut output = String::new();
+        self.options
+            .write_html(&mut output, &left, &right)
+            .expect("writing to a string cannot fail");
+        output
+    }
+
+    /// Rewrite each string with a [`Normalizer`], before diffing.
+    ///
+    /// # Features
+    ///
+    /// Requires the `regex` feature to be enabled.
+    #[cfg(feature = "regex")]
+    pub fn normalize(mut self, normalizer: Normalizer) -> StrComparison<'a, TLeft, TRight> {
+        self.normalizers.push(normalizer);
+        self
+    }
+
+    /// The text of each string which is diffed, once normalized.
+    fn diff_text(&self) -> (Cow<'_, str>, Cow<'_, str>) {
+        let (left, right) = (self.left.as_ref(), self.right.as_ref());
+        #[cfg(feature = "regex")]
+        let (left, right) = (
+            normalize::normalize(left, &self.normalizers),
+            normalize::normalize(right, &self.normalizers),
+        );
+        #[cfg(not(feature = "regex"))]
+        let (left, right) = (Cow::Borrowed(left), Cow::Borrowed(right));
+        (left, right)
     }
 }
 
@@ -195,10 +578,65 @@ impl<TLeft, TRight> Display for StrComparison<'_, TLeft, TRight>
 where
     TLeft: AsRef<str> + ?Sized,
     TRight: AsRef<str> + ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (left, right) = self.diff_text();
+        printer::write_header(f)?;
+        self.options.write_lines(f, &left, &right)
+    }
+}
+
+/// A comparison of two byte slices.
+///
+/// In contrast to [`Comparison`], which uses the [`core::fmt::Debug`] representation,
+/// `BytesComparison` renders each value as a hex dump, in the style of `xxd`.
+/// Each row shows the offset, sixteen bytes in hex, and the same bytes in ASCII.
+///
+/// ```
+/// use pretty_assertions::BytesComparison;
+///
+/// print!("{}", BytesComparison::new(b"Hello World!", b"Hello Wrold!"));
+/// ```
+///
+/// Rows are diffed as a whole. Where a single row has changed, the bytes which
+/// differ are highlighted.
+///
+/// ## Value type bounds
+///
+/// Any value that can be referenced as a byte slice via [`AsRef`] may be used,
+/// such as `Vec<u8>`, `[u8; N]` or `str`.
+///
+/// The values may have different types, although in practice they are usually the same.
+pub struct BytesComparison<'a, TLeft, TRight>
+where
+    TLeft: ?Sized,
+    TRight: ?Sized,
+{
+    left: &'a TLeft,
+    right: &'a TRight,
+}
+
+impl<'a, TLeft, TRight> BytesComparison<'a, TLeft, TRight>
+where
+    TLeft: AsRef<[u8]> + ?Sized,
+    TRight: AsRef<[u8]> + ?Sized,
+{
+    /// Store two values to be compared in future.
+    ///
+    /// Expensive diffing is deferred until calling `Display::fmt`.
+    pub fn new(left: &'a TLeft, right: &'a TRight) -> BytesComparison<'a, TLeft, TRight> {
+        BytesComparison { left, right }
+    }
+}
+
+impl<TLeft, TRight> Display for BytesComparison<'_, TLeft, TRight>
+where
+    TLeft: AsRef<[u8]> + ?Sized,
+    TRight: AsRef<[u8]> + ?Sized,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         printer::write_header(f)?;
-        printer::write_lines(f, self.left.as_ref(), self.right.as_ref())
+        printer::write_bytes(f, self.left.as_ref(), self.right.as_ref())
     }
 }
 
@@ -234,14 +672,70 @@ macro_rules! assert_eq {
             (left_val, right_val) => {
                 if !(*left_val == *right_val) {
                     use $crate::private::CreateComparison;
-                    ::core::panic!("assertion failed: `(left == right)`{}{}\
+                    $crate::private::fail(::core::format_args!("assertion failed: `(left == right)`{}{}\
                        \n\
                        \n{}\
                        \n",
                        $maybe_colon,
                        format_args!($($arg)*),
                        (left_val, right_val).create_comparison()
-                    )
+                    ))
+                }
+            }
+        }
+    });
+}
+
+/// Asserts that two expressions have the same [`Debug`] representation, once redacted.
+///
+/// Each item in the `ignore` list is a [`Redaction`], or converts into one: a string
+/// starting with `.` is a path such as `".user.created_at"`, and any other string is a
+/// field name, which is redacted at any depth. Redacted values are replaced with
+/// `[redacted]` before the values are compared, so they are ignored by the assertion.
+///
+/// On panic, this macro will print a diff of the redacted representations, in the same
+/// way as [`assert_eq!`]. You can provide a custom panic message if desired.
+///
+/// # Examples
+///
+/// ```
+/// use pretty_assertions::assert_eq_redacted;
+///
+/// #[derive(Debug)]
+/// struct Event {
+///     id: u64,
+///     created_at: u64,
+///     name: &'static str,
+/// }
+///
+/// let a = Event { id: 1, created_at: 1700000000, name: "signup" };
+/// let b = Event { id: 2, created_at: 1700000042, name: "signup" };
+/// assert_eq_redacted!(a, b, ignore = [".created_at", ".id"]);
+///
+/// assert_eq_redacted!(a, b, ignore = ["id", "created_at"], "we are testing {}", a.name);
+/// ```
+#[macro_export]
+macro_rules! assert_eq_redacted {
+    ($left:expr, $right:expr, ignore = [$($redaction:expr),* $(,)?]$(,)?) => ({
+        $crate::assert_eq_redacted!(@ $left, $right, [$($redaction),*], "", "");
+    });
+    ($left:expr, $right:expr, ignore = [$($redaction:expr),* $(,)?], $($arg:tt)+) => ({
+        $crate::assert_eq_redacted!(@ $left, $right, [$($redaction),*], ": ", $($arg)+);
+    });
+    (@ $left:expr, $right:expr, [$($redaction:expr),*], $maybe_colon:expr, $($arg:tt)*) => ({
+        match (&($left), &($right)) {
+            (left_val, right_val) => {
+                let comparison = $crate::Comparison::new(left_val, right_val)
+                    $(.redact($redaction))*;
+                if !comparison.is_equal() {
+                    $crate::private::fail(::core::format_args!("assertion failed: `(left == right)`{}{}\
+                       \n\
+                       \n{}\
+                       \n",
+                       $maybe_colon,
+                       format_args!($($arg)*),
+                       comparison
+                    ))
                 }
             }
         }
@@ -267,123 +761,1131 @@ macro_rules! assert_eq {
 ///
 /// assert_str_eq!(a, b, "we are testing concatenation with {} and {}", a, b);
 /// ```
+///
+/// Differences in case and whitespace can be ignored by listing options, named after the
+/// methods of [`StrComparison`]: `ignore_case`, `collapse_whitespace`, `trim_lines` and
+/// `ignore_blank_lines`. The diff still shows the original text.
+///
+/// ```
+/// use pretty_assertions::assert_str_eq;
+///
+/// let a = "SELECT id\n  FROM users\n";
+/// let b = "select id\nfrom users";
+/// assert_str_eq!(a, b, options = [ignore_case, trim_lines, ignore_blank_lines]);
+///
+/// assert_str_eq!(a, b, options = [ignore_case, trim_lines, ignore_blank_lines], "in {}", "query");
+/// ```
+///
+/// # Updating expected values
+///
+/// When the `PRETTY_ASSERTIONS_UPDATE` environment variable is set to `1`, and the second
+/// argument is a string literal, a failing assertion rewrites that literal in the source
+/// file with the actual value, and passes. This makes it quick to update many expected
+/// strings after an intentional change in output. Review the changes made to your source
+/// files before committing them.
+///
+/// Updating requires the `std` feature, and the source file must be found in the package
+/// directory or one of its parents. An assertion nested in another macro cannot be
+/// updated, as its location is reported as that of the outer macro. If the literal found
+/// there is not the expected value, the assertion panics and the file is left unchanged.
 #[macro_export]
 macro_rules! assert_str_eq {
     ($left:expr, $right:expr$(,)?) => ({
         $crate::assert_str_eq!(@ $left, $right, "", "");
     });
+    ($left:expr, $right:expr, options = [$($option:ident),* $(,)?]$(,)?) => ({
+        $crate::assert_str_eq!(@ $left, $right, [$($option),*], "", "");
+    });
+    ($left:expr, $right:expr, options = [$($option:ident),* $(,)?], $($arg:tt)+) => ({
+        $crate::assert_str_eq!(@ $left, $right, [$($option),*], ": ", $($arg)+);
+    });
     ($left:expr, $right:expr, $($arg:tt)*) => ({
         $crate::assert_str_eq!(@ $left, $right, ": ", $($arg)+);
     });
+    (@ $left:expr, $right:expr, [$($option:ident),*], $maybe_colon:expr, $($arg:tt)*) => ({
+        match (&($left), &($right)) {
+            (left_val, right_val) => {
+                let comparison = $crate::StrComparison::new(left_val, right_val)$(.$option())*;
+                if !comparison.is_equal() {
+                    $crate::private::fail(::core::format_args!("assertion failed: `(left == right)`{}{}\
+                       \n\
+                       \n{}\
+                       \n",
+                       $maybe_colon,
+                       format_args!($($arg)*),
+                       comparison
+                    ))
+                }
+            }
+        }
+    });
     (@ $left:expr, $right:expr, $maybe_colon:expr, $($arg:tt)*) => ({
         match (&($left), &($right)) {
             (left_val, right_val) => {
-                if !(*left_val == *right_val) {
-                    ::core::panic!("assertion failed: `(left == right)`{}{}\
+                if !(*left_val == *right_val) && !$crate::private::update_literal(
+                    ::core::option_env!("CARGO_MANIFEST_DIR"),
+                    ::core::file!(),
+                    ::core::line!(),
+                    ::core::column!(),
+                    ::core::convert::AsRef::<str>::as_ref(right_val),
+                    ::core::convert::AsRef::<str>::as_ref(left_val),
+                ) {
+                    $crate::private::fail(::core::format_args!("assertion failed: `(left == right)`{}{}\
                        \n\
                        \n{}\
                        \n",
                        $maybe_colon,
                        format_args!($($arg)*),
                        $crate::StrComparison::new(left_val, right_val)
-                    )
+                    ))
                 }
             }
         }
     });
 }
 
-/// Asserts that two expressions are not equal to each other (using [`PartialEq`]).
+/// Asserts that two expressions are equal to each other (using [`PartialEq`]).
 ///
-/// On panic, this macro will print the values of the expressions with their
-/// [`Debug`] representations.
+/// On panic, this macro will print a diff of each value rendered as a hex dump.
+/// See [`BytesComparison`] for further details.
 ///
-/// This is a drop in replacement for [`core::assert_ne!`].
+/// This is a drop in replacement for [`core::assert_eq!`].
 /// You can provide a custom panic message if desired.
 ///
 /// # Examples
 ///
 /// ```
-/// use pretty_assertions::assert_ne;
+/// use pretty_assertions::assert_bytes_eq;
 ///
-/// let a = 3;
-/// let b = 2;
-/// assert_ne!(a, b);
+/// let a = vec![0, 1, b'f', b'o', b'o'];
+/// let b = b"\x00\x01foo";
+/// assert_bytes_eq!(a, b);
 ///
-/// assert_ne!(a, b, "we are testing that the values are not equal");
+/// assert_bytes_eq!(a, b, "we are testing {:?} and {:?}", a, b);
 /// ```
 #[macro_export]
-macro_rules! assert_ne {
+macro_rules! assert_bytes_eq {
     ($left:expr, $right:expr$(,)?) => ({
-        $crate::assert_ne!(@ $left, $right, "", "");
+        $crate::assert_bytes_eq!(@ $left, $right, "", "");
     });
-    ($left:expr, $right:expr, $($arg:tt)+) => ({
-        $crate::assert_ne!(@ $left, $right, ": ", $($arg)+);
+    ($left:expr, $right:expr, $($arg:tt)*) => ({
+        $crate::assert_bytes_eq!(@ $left, $right, ": ", $($arg)+);
     });
-    (@ $left:expr, $right:expr, $maybe_colon:expr, $($arg:tt)+) => ({
+    (@ $left:expr, $right:expr, $maybe_colon:expr, $($arg:tt)*) => ({
         match (&($left), &($right)) {
             (left_val, right_val) => {
-                if *left_val == *right_val {
-                    ::core::panic!("assertion failed: `(left != right)`{}{}\
-                        \n\
-                        \nBoth sides:\
-                        \n{:#?}\
-                        \n\
-                        \n",
-                        $maybe_colon,
-                        format_args!($($arg)+),
-                        left_val
-                    )
+                if !(*left_val == *right_val) {
+                    $crate::private::fail(::core::format_args!("assertion failed: `(left == right)`{}{}\
+                       \n\
+                       \n{}\
+                       \n",
+                       $maybe_colon,
+                       format_args!($($arg)*),
+                       $crate::BytesComparison::new(left_val, right_val)
+                    ))
                 }
             }
         }
     });
 }
 
-/// Asserts that a value matches a pattern.
+/// Asserts that two iterators yield equal elements (using [`PartialEq`]).
 ///
-/// On panic, this macro will print a diff derived from [`Debug`] representation of
-/// the value, and a string representation of the pattern.
+/// Both iterators are consumed and compared element by element, without collecting
+/// them. Any values implementing [`IntoIterator`] may be given.
+///
+/// On panic, this macro will print the index of the first difference, the length of
+/// each iterator if they differ, and a diff derived from the [`Debug`] representation
+/// of the elements around the first difference. At most a thousand elements are counted
+/// after the first difference, so an unbounded iterator is reported as having at least
+/// that many.
 ///
-/// This is a drop in replacement for [`core::assert_matches::assert_matches!`].
 /// You can provide a custom panic message if desired.
 ///
 /// # Examples
 ///
 /// ```
-/// use pretty_assertions::assert_matches;
+/// use pretty_assertions::assert_iter_eq;
 ///
-/// let a = Some(3);
-/// assert_matches!(a, Some(_));
+/// let a = (1..4).map(|x| x * 2);
+/// let b = [2, 4, 6];
+/// assert_iter_eq!(a, b.iter().copied());
+///
+/// assert_iter_eq!(0..3, vec![0, 1, 2], "we are testing ranges");
+/// ```
+#[macro_export]
+macro_rules! assert_iter_eq {
+    ($left:expr, $right:expr$(,)?) => ({
+        $crate::assert_iter_eq!(@ $left, $right, "", "");
+    });
+    ($left:expr, $right:expr, $($arg:tt)*) => ({
+        $crate::assert_iter_eq!(@ $left, $right, ": ", $($arg)+);
+    });
+    (@ $left:expr, $right:expr, $maybe_colon:expr, $($arg:tt)*) => ({
+        if let ::core::result::Result::Err(mismatch) = $crate::private::iter_eq($left, $right) {
+            $crate::private::fail(::core::format_args!("assertion failed: `(left == right)`{}{}\
+               \n\
+               \n{}\
+               \n",
+               $maybe_colon,
+               format_args!($($arg)*),
+               mismatch
+            ))
+        }
+    });
+}
+
+/// Asserts that two JSON values are equal to each other.
+///
+/// On panic, this macro will print a diff of each value as pretty-printed JSON,
+/// with the keys of every object sorted. See [`JsonComparison`] for further details.
+///
+/// You can provide a custom panic message if desired.
+///
+/// # Examples
 ///
-/// assert_matches!(a, Some(value) if value > 2, "we are testing {:?} with a pattern", a);
 /// ```
+/// use pretty_assertions::assert_json_eq;
+/// use serde_json::json;
 ///
-/// # Features
+/// let a = json!({"name": "Alice", "age": 42});
+/// let b = json!({"age": 42, "name": "Alice"});
+/// assert_json_eq!(a, b);
 ///
-/// Requires the `unstable` feature to be enabled.
+/// assert_json_eq!(a, b, "we are testing {}", a);
+/// ```
 ///
-/// **Please note:** implementation under the `unstable` feature may be changed between
-/// patch versions without warning.
-#[cfg(feature = "unstable")]
+/// # Features
+///
+/// Requires the `serde_json` feature to be enabled.
+#[cfg(feature = "serde_json")]
 #[macro_export]
-macro_rules! assert_matches {
-    ($left:expr, $( $pattern:pat )|+ $( if $guard: expr )? $(,)?) => ({
-        match $left {
-            $( $pattern )|+ $( if $guard )? => {}
-            ref left_val => {
-                $crate::assert_matches!(
-                    @
-                    left_val,
-                    ::core::stringify!($($pattern)|+ $(if $guard)?),
-                    "",
-                    ""
-                );
-            }
-        }
+macro_rules! assert_json_eq {
+    ($left:expr, $right:expr$(,)?) => ({
+        $crate::assert_json_eq!(@ $left, $right, "", "");
     });
-    ($left:expr, $( $pattern:pat )|+ $( if $guard: expr )?, $($arg:tt)+) => ({
-        match $left {
+    ($left:expr, $right:expr, $($arg:tt)*) => ({
+        $crate::assert_json_eq!(@ $left, $right, ": ", $($arg)+);
+    });
+    (@ $left:expr, $right:expr, $maybe_colon:expr, $($arg:tt)*) => ({
+        match (&($left), &($right)) {
+            (left_val, right_val) => {
+                let comparison = $crate::JsonComparison::new(left_val, right_val);
+                if !comparison.is_equal() {
+                    $crate::private::fail(::core::format_args!("assertion failed: `(left == right)`{}{}\
+                       \n\
+                       \n{}\
+                       \n",
+                       $maybe_colon,
+                       format_args!($($arg)*),
+                       comparison
+                    ))
+                }
+            }
+        }
+    });
+}
+
+/// Asserts that the left JSON value includes the right JSON value.
+///
+/// Fields of an object on the left which are not present on the right are ignored.
+/// Otherwise, this behaves the same as [`assert_json_eq!`].
+/// See [`JsonComparison::ignore_extra_fields`] for further details.
+///
+/// You can provide a custom panic message if desired.
+///
+/// # Examples
+///
+/// ```
+/// use pretty_assertions::assert_json_include;
+/// use serde_json::json;
+///
+/// let actual = json!({"id": 1234, "name": "Alice", "roles": [{"id": 5, "name": "admin"}]});
+/// let expected = json!({"name": "Alice", "roles": [{"name": "admin"}]});
+/// assert_json_include!(actual, expected);
+///
+/// assert_json_include!(actual, expected, "we are testing {}", actual);
+/// ```
+///
+/// # Features
+///
+/// Requires the `serde_json` feature to be enabled.
+#[cfg(feature = "serde_json")]
+#[macro_export]
+macro_rules! assert_json_include {
+    ($left:expr, $right:expr$(,)?) => ({
+        $crate::assert_json_include!(@ $left, $right, "", "");
+    });
+    ($left:expr, $right:expr, $($arg:tt)*) => ({
+        $crate::assert_json_include!(@ $left, $right, ": ", $($arg)+);
+    });
+    (@ $left:expr, $right:expr, $maybe_colon:expr, $($arg:tt)*) => ({
+        match (&($left), &($right)) {
+            (left_val, right_val) => {
+                let comparison = $crate::JsonComparison::new(left_val, right_val)
+                    .ignore_extra_fields();
+                if !comparison.is_equal() {
+                    $crate::private::fail(::core::format_args!("assertion failed: `(left includes right)`{}{}\
+                       \n\
+                       \n{}\
+                       \n",
+                       $maybe_colon,
+                       format_args!($($arg)*),
+                       comparison
+                    ))
+                }
+            }
+        }
+    });
+}
+
+/// Asserts that two expressions are equal to each other (using [`PartialEq`]).
+///
+/// On panic, this macro will print a diff derived from the [`Serialize`](::serde::Serialize)
+/// implementation of each value, rather than from its [`Debug`] representation.
+/// This is useful where `Debug` is hand-written, or omits details, and makes the output
+/// of maps such as `HashMap` deterministic. See [`SerdeComparison`] for further details.
+///
+/// You can provide a custom panic message if desired.
+///
+/// # Examples
+///
+/// ```
+/// use pretty_assertions::assert_serde_eq;
+/// use serde::Serialize;
+///
+/// #[derive(PartialEq, Serialize)]
+/// struct User {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// let a = User { name: "Alice".into(), age: 42 };
+/// let b = User { name: "Alice".into(), age: 40 + 2 };
+/// assert_serde_eq!(a, b);
+///
+/// assert_serde_eq!(a, b, "we are testing {}", a.name);
+/// ```
+///
+/// The elements of a `HashSet` are serialized in no particular order. They can be sorted
+/// by listing options, named after the methods of [`SerdeComparison`]: `sort_sequences`.
+///
+/// ```
+/// use pretty_assertions::assert_serde_eq;
+/// use std::collections::HashSet;
+///
+/// let a: HashSet<u32> = (0..10).collect();
+/// let b: HashSet<u32> = (0..10).rev().collect();
+/// assert_serde_eq!(a, b, options = [sort_sequences]);
+///
+/// assert_serde_eq!(a, b, options = [sort_sequences], "in {}", "set");
+/// ```
+///
+/// # Features
+///
+/// Requires the `serde` feature to be enabled.
+#[cfg(feature = "serde")]
+#[macro_export]
+macro_rules! assert_serde_eq {
+    ($left:expr, $right:expr$(,)?) => ({
+        $crate::assert_serde_eq!(@ $left, $right, [], "", "");
+    });
+    ($left:expr, $right:expr, options = [$($option:ident),* $(,)?]$(,)?) => ({
+        $crate::assert_serde_eq!(@ $left, $right, [$($option),*], "", "");
+    });
+    ($left:expr, $right:expr, options = [$($option:ident),* $(,)?], $($arg:tt)+) => ({
+        $crate::assert_serde_eq!(@ $left, $right, [$($option),*], ": ", $($arg)+);
+    });
+    ($left:expr, $right:expr, $($arg:tt)*) => ({
+        $crate::assert_serde_eq!(@ $left, $right, [], ": ", $($arg)+);
+    });
+    (@ $left:expr, $right:expr, [$($option:ident),*], $maybe_colon:expr, $($arg:tt)*) => ({
+        match (&($left), &($right)) {
+            (left_val, right_val) => {
+                if !(*left_val == *right_val) {
+                    $crate::private::fail(::core::format_args!("assertion failed: `(left == right)`{}{}\
+                       \n\
+                       \n{}\
+                       \n",
+                       $maybe_colon,
+                       format_args!($($arg)*),
+                       $crate::SerdeComparison::new(left_val, right_val)$(.$option())*
+                    ))
+                }
+            }
+        }
+    });
+}
+
+/// Asserts that two YAML documents are equal to each other.
+///
+/// Both documents are parsed, so that cosmetic differences such as quoting style and the
+/// order of keys are ignored. On panic, this macro will print each value which differs,
+/// along with its key path. Text holding several documents separated by `---` is compared
+/// document by document. See [`YamlComparison`] for further details.
+///
+/// If either document fails to parse, the text of each is compared instead, in the same
+/// way as [`assert_str_eq!`].
+///
+/// You can provide a custom panic message if desired.
+///
+/// # Examples
+///
+/// ```
+/// use pretty_assertions::assert_yaml_eq;
+///
+/// let a = "name: web\nports: [80, 443]\n";
+/// let b = "ports:\n  - 80\n  - 443\nname: 'web'\n";
+/// assert_yaml_eq!(a, b);
+///
+/// assert_yaml_eq!(a, b, "we are testing {}", "config");
+/// ```
+///
+/// # Features
+///
+/// Requires the `serde_yaml` feature to be enabled.
+#[cfg(feature = "serde_yaml")]
+#[macro_export]
+macro_rules! assert_yaml_eq {
+    ($left:expr, $right:expr$(,)?) => ({
+        $crate::assert_yaml_eq!(@ $left, $right, "", "");
+    });
+    ($left:expr, $right:expr, $($arg:tt)*) => ({
+        $crate::assert_yaml_eq!(@ $left, $right, ": ", $($arg)+);
+    });
+    (@ $left:expr, $right:expr, $maybe_colon:expr, $($arg:tt)*) => ({
+        match (&($left), &($right)) {
+            (left_val, right_val) => {
+                let comparison = $crate::YamlComparison::new(
+                    ::core::convert::AsRef::<str>::as_ref(left_val),
+                    ::core::convert::AsRef::<str>::as_ref(right_val),
+                );
+                if !comparison.is_equal() {
+                    $crate::private::fail(::core::format_args!("assertion failed: `(left == right)`{}{}\
+                       \n\
+                       \n{}\
+                       \n",
+                       $maybe_colon,
+                       format_args!($($arg)*),
+                       comparison
+                    ))
+                }
+            }
+        }
+    });
+}
+
+/// Asserts that two TOML documents are equal to each other.
+///
+/// Both documents are parsed, so that cosmetic differences such as quoting style and the
+/// order of keys are ignored. On panic, this macro will print each value which differs,
+/// along with its key path. See [`TomlComparison`] for further details.
+///
+/// If either document fails to parse, the text of each is compared instead, in the same
+/// way as [`assert_str_eq!`].
+///
+/// You can provide a custom panic message if desired.
+///
+/// # Examples
+///
+/// ```
+/// use pretty_assertions::assert_toml_eq;
+///
+/// let a = "[package]\nname = \"foo\"\nversion = \"0.1.0\"\n";
+/// let b = "package = { version = '0.1.0', name = 'foo' }\n";
+/// assert_toml_eq!(a, b);
+///
+/// assert_toml_eq!(a, b, "we are testing {}", "config");
+/// ```
+///
+/// # Features
+///
+/// Requires the `toml` feature to be enabled.
+#[cfg(feature = "toml")]
+#[macro_export]
+macro_rules! assert_toml_eq {
+    ($left:expr, $right:expr$(,)?) => ({
+        $crate::assert_toml_eq!(@ $left, $right, "", "");
+    });
+    ($left:expr, $right:expr, $($arg:tt)*) => ({
+        $crate::assert_toml_eq!(@ $left, $right, ": ", $($arg)+);
+    });
+    (@ $left:expr, $right:expr, $maybe_colon:expr, $($arg:tt)*) => ({
+        match (&($left), &($right)) {
+            (left_val, right_val) => {
+                let comparison = $crate::TomlComparison::new(
+                    ::core::convert::AsRef::<str>::as_ref(left_val),
+                    ::core::convert::AsRef::<str>::as_ref(right_val),
+                );
+                if !comparison.is_equal() {
+                    $crate::private::fail(::core::format_args!("assertion failed: `(left == right)`{}{}\
+                       \n\
+                       \n{}\
+                       \n",
+                       $maybe_colon,
+                       format_args!($($arg)*),
+                       comparison
+                    ))
+                }
+            }
+        }
+    });
+}
+
+/// Asserts that two HTML documents are equal to each other, once normalized.
+///
+/// Both documents are parsed, their attributes sorted and their whitespace collapsed,
+/// then pretty-printed with one element per line. On panic, this macro will print the
+/// path of each element which differs, followed by a diff of the pretty-printed documents.
+/// See [`HtmlComparison`] for further details.
+///
+/// If either document fails to parse, the text of each is compared instead, in the same
+/// way as [`assert_str_eq!`].
+///
+/// You can provide a custom panic message if desired.
+///
+/// # Examples
+///
+/// ```
+/// use pretty_assertions::assert_html_eq;
+///
+/// let a = r#"<p class="intro" id="top">Hello   world</p>"#;
+/// let b = "<p id='top' class='intro'>\n  Hello world\n</p>";
+/// assert_html_eq!(a, b);
+///
+/// assert_html_eq!(a, b, "we are testing {}", "templates");
+/// ```
+///
+/// # Features
+///
+/// Requires the `tl` feature to be enabled.
+#[cfg(feature = "tl")]
+#[macro_export]
+macro_rules! assert_html_eq {
+    ($left:expr, $right:expr$(,)?) => ({
+        $crate::assert_html_eq!(@ $left, $right, "", "");
+    });
+    ($left:expr, $right:expr, $($arg:tt)*) => ({
+        $crate::assert_html_eq!(@ $left, $right, ": ", $($arg)+);
+    });
+    (@ $left:expr, $right:expr, $maybe_colon:expr, $($arg:tt)*) => ({
+        match (&($left), &($right)) {
+            (left_val, right_val) => {
+                let comparison = $crate::HtmlComparison::new(
+                    ::core::convert::AsRef::<str>::as_ref(left_val),
+                    ::core::convert::AsRef::<str>::as_ref(right_val),
+                );
+                if !comparison.is_equal() {
+                    $crate::private::fail(::core::format_args!("assertion failed: `(left == right)`{}{}\
+                       \n\
+                       \n{}\
+                       \n",
+                       $maybe_colon,
+                       format_args!($($arg)*),
+                       comparison
+                    ))
+                }
+            }
+        }
+    });
+}
+
+/// Asserts that two XML documents are equal to each other, once normalized.
+///
+/// Both documents are parsed, their attributes sorted and their whitespace collapsed,
+/// then pretty-printed with one element per line. On panic, this macro will print the
+/// path of each element which differs, followed by a diff of the pretty-printed documents.
+/// See [`XmlComparison`] for further details.
+///
+/// If either document fails to parse, the text of each is compared instead, in the same
+/// way as [`assert_str_eq!`].
+///
+/// You can provide a custom panic message if desired.
+///
+/// # Examples
+///
+/// ```
+/// use pretty_assertions::assert_xml_eq;
+///
+/// let a = r#"<entry id="1" lang="en"><title>Hello</title></entry>"#;
+/// let b = "<entry lang='en' id='1'>\n  <title>Hello</title>\n</entry>";
+/// assert_xml_eq!(a, b);
+///
+/// assert_xml_eq!(a, b, "we are testing {}", "templates");
+/// ```
+///
+/// # Features
+///
+/// Requires the `roxmltree` feature to be enabled.
+#[cfg(feature = "roxmltree")]
+#[macro_export]
+macro_rules! assert_xml_eq {
+    ($left:expr, $right:expr$(,)?) => ({
+        $crate::assert_xml_eq!(@ $left, $right, "", "");
+    });
+    ($left:expr, $right:expr, $($arg:tt)*) => ({
+        $crate::assert_xml_eq!(@ $left, $right, ": ", $($arg)+);
+    });
+    (@ $left:expr, $right:expr, $maybe_colon:expr, $($arg:tt)*) => ({
+        match (&($left), &($right)) {
+            (left_val, right_val) => {
+                let comparison = $crate::XmlComparison::new(
+                    ::core::convert::AsRef::<str>::as_ref(left_val),
+                    ::core::convert::AsRef::<str>::as_ref(right_val),
+                );
+                if !comparison.is_equal() {
+                    $crate::private::fail(::core::format_args!("assertion failed: `(left == right)`{}{}\
+                       \n\
+                       \n{}\
+                       \n",
+                       $maybe_colon,
+                       format_args!($($arg)*),
+                       comparison
+                    ))
+                }
+            }
+        }
+    });
+}
+
+/// Asserts that a value matches its snapshot, stored in a file.
+///
+/// The snapshot of a value is its [`str`] representation if it is a string, or its
+/// `{:#?}` representation otherwise. Snapshots are stored under the `snapshots` directory
+/// of the package being tested, in a file named after the module path and the function
+/// the assertion is made in, such as `snapshots/my_crate__tests__renders.snap`. Each
+/// snapshot taken after the first in the same function, during one test, is numbered in
+/// order, such as `snapshots/my_crate__tests__renders-2.snap`.
+///
+/// On panic, this macro will print a diff from the snapshot to the value, in the same way
+/// as [`assert_str_eq!`]. You can provide a custom panic message if desired.
+///
+/// When the `PRETTY_ASSERTIONS_UPDATE` environment variable is set to `1`, the snapshot
+/// file is written with the value instead, and the assertion passes. This creates any
+/// missing snapshots, and updates any which have changed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use pretty_assertions::assert_snapshot;
+///
+/// #[derive(Debug)]
+/// struct Config {
+///     name: &'static str,
+///     retries: u32,
+/// }
+///
+/// let config = Config { name: "web", retries: 3 };
+/// assert_snapshot!(config);
+///
+/// // Stored in a second snapshot file
+/// assert_snapshot!(config.name, "we are testing {}", config.name);
+/// ```
+///
+/// # Features
+///
+/// Requires the `std` feature to be enabled.
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($value:expr$(,)?) => ({
+        $crate::assert_snapshot!(@ $value, "", "");
+    });
+    ($value:expr, $($arg:tt)*) => ({
+        $crate::assert_snapshot!(@ $value, ": ", $($arg)+);
+    });
+    (@ $value:expr, $maybe_colon:expr, $($arg:tt)*) => ({
+        fn f() {}
+        match &($value) {
+            value => {
+                use $crate::private::SnapshotText;
+                let actual = (value,).snapshot_text();
+                let function = $crate::private::type_name_of(f);
+                let manifest_dir = ::core::env!("CARGO_MANIFEST_DIR");
+                if let ::core::result::Result::Err(mismatch) =
+                    $crate::private::assert_snapshot(manifest_dir, function, &actual)
+                {
+                    $crate::private::fail(::core::format_args!("assertion failed: `(snapshot == value)`{}{}\
+                       \n\
+                       \n{}\
+                       \n",
+                       $maybe_colon,
+                       format_args!($($arg)*),
+                       mismatch
+                    ))
+                }
+            }
+        }
+    });
+}
+
+/// Asserts that a value is equal to the contents of a file.
+///
+/// The path is relative to the directory of the package being tested, as given by
+/// `CARGO_MANIFEST_DIR`. The value is compared by its [`str`] representation if it is a
+/// string, or its `{:#?}` representation otherwise.
+///
+/// On panic, this macro will print the lines of the file which differ, and a diff from
+/// the file to the value, in the same way as [`assert_str_eq!`].
+/// You can provide a custom panic message if desired.
+///
+/// Windows line endings in the file are read as `\n`, so the assertion is not affected by
+/// how the file is checked out.
+///
+/// When the `PRETTY_ASSERTIONS_UPDATE` environment variable is set to `1`, the file is
+/// written with the value instead, and the assertion passes.
+///
+/// # Examples
+///
+/// ```no_run
+/// use pretty_assertions::assert_file_eq;
+///
+/// let report = ["name: web", "status: ok"].join("\n");
+/// assert_file_eq!(report, "tests/golden/report.expected");
+///
+/// assert_file_eq!(report, "tests/golden/report.expected", "we are testing {}", "reports");
+/// ```
+///
+/// # Features
+///
+/// Requires the `std` feature to be enabled.
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! assert_file_eq {
+    ($value:expr, $path:expr$(,)?) => ({
+        $crate::assert_file_eq!(@ $value, $path, "", "");
+    });
+    ($value:expr, $path:expr, $($arg:tt)*) => ({
+        $crate::assert_file_eq!(@ $value, $path, ": ", $($arg)+);
+    });
+    (@ $value:expr, $path:expr, $maybe_colon:expr, $($arg:tt)*) => ({
+        match (&($value), &($path)) {
+            (value, path) => {
+                use $crate::private::SnapshotText;
+                let actual = (value,).snapshot_text();
+                let manifest_dir = ::core::env!("CARGO_MANIFEST_DIR");
+                if let ::core::result::Result::Err(mismatch) =
+                    $crate::private::assert_file(manifest_dir, path, &actual)
+                {
+                    $crate::private::fail(::core::format_args!("assertion failed: `(file == value)`{}{}\
+                       \n\
+                       \n{}\
+                       \n",
+                       $maybe_colon,
+                       format_args!($($arg)*),
+                       mismatch
+                    ))
+                }
+            }
+        }
+    });
+}
+
+/// Asserts that two expressions are not equal to each other (using [`PartialEq`]).
+///
+/// On panic, this macro will print the values of the expressions with their
+/// [`Debug`] representations.
+///
+/// This is a drop in replacement for [`core::assert_ne!`].
+/// You can provide a custom panic message if desired.
+///
+/// # Examples
+///
+/// ```
+/// use pretty_assertions::assert_ne;
+///
+/// let a = 3;
+/// let b = 2;
+/// assert_ne!(a, b);
+///
+/// assert_ne!(a, b, "we are testing that the values are not equal");
+/// ```
+#[macro_export]
+macro_rules! assert_ne {
+    ($left:expr, $right:expr$(,)?) => ({
+        $crate::assert_ne!(@ $left, $right, "", "");
+    });
+    ($left:expr, $right:expr, $($arg:tt)+) => ({
+        $crate::assert_ne!(@ $left, $right, ": ", $($arg)+);
+    });
+    (@ $left:expr, $right:expr, $maybe_colon:expr, $($arg:tt)+) => ({
+        match (&($left), &($right)) {
+            (left_val, right_val) => {
+                if *left_val == *right_val {
+                    $crate::private::fail(::core::format_args!("assertion failed: `(left != right)`{}{}\
+                        \n\
+                        \nBoth sides:\
+                        \n{:#?}\
+                        \n\
+                        \n",
+                        $maybe_colon,
+                        format_args!($($arg)+),
+                        left_val
+                    ))
+                }
+            }
+        }
+    });
+}
+
+/// Checks that two expressions are equal to each other (using [`PartialEq`]), without panicking.
+///
+/// Evaluates to `Ok(())` if the values are equal. Otherwise, evaluates to `Err(`[`Mismatch`]`)`,
+/// which displays as a diff derived from the [`Debug`] representation of each value.
+/// See [`check_eq()`] for further details.
+///
+/// You can provide a custom message if desired, which is attached to the [`Mismatch`].
+///
+/// # Examples
+///
+/// ```
+/// use pretty_assertions::{check_eq, Mismatch};
+///
+/// fn test() -> Result<(), Mismatch> {
+///     let a = 3;
+///     let b = 1 + 2;
+///     check_eq!(a, b)?;
+///
+///     check_eq!(a, b, "we are testing addition with {} and {}", a, b)?;
+///     Ok(())
+/// }
+/// # test().unwrap();
+/// ```
+#[macro_export]
+macro_rules! check_eq {
+    ($left:expr, $right:expr$(,)?) => ({
+        $crate::check_eq(&($left), &($right))
+    });
+    ($left:expr, $right:expr, $($arg:tt)+) => ({
+        $crate::check_eq(&($left), &($right))
+            .map_err(|mismatch| mismatch.with_message(format_args!($($arg)+)))
+    });
+}
+
+/// Asserts that two expressions are equal to each other (using [`PartialEq`]), without
+/// stopping at the first failure.
+///
+/// Failures are recorded in the given [`SoftAssertions`] scope, together with the location
+/// of the assertion. The scope panics once with every recorded diff when it is finished
+/// or dropped.
+///
+/// You can provide a custom panic message if desired.
+///
+/// # Examples
+///
+/// ```
+/// use pretty_assertions::{soft_assert_eq, SoftAssertions};
+///
+/// let mut soft = SoftAssertions::new();
+/// let a = 3;
+/// let b = 1 + 2;
+/// soft_assert_eq!(soft, a, b);
+///
+/// soft_assert_eq!(soft, a, b, "we are testing addition with {} and {}", a, b);
+/// soft.finish();
+/// ```
+#[macro_export]
+macro_rules! soft_assert_eq {
+    ($soft:expr, $left:expr, $right:expr$(,)?) => ({
+        $soft.record($crate::check_eq!($left, $right))
+    });
+    ($soft:expr, $left:expr, $right:expr, $($arg:tt)+) => ({
+        $soft.record($crate::check_eq!($left, $right, $($arg)+))
+    });
+}
+
+/// Asserts that a [`Result`] is [`Ok`], optionally with an expected inner value (using [`PartialEq`]).
+///
+/// If the result is an [`Err`], this macro will print the error with its [`Debug`]
+/// representation. If an expected value is given and the result is [`Ok`], it will
+/// print a diff of the inner value only, in the same way as [`assert_eq!`].
+///
+/// You can provide a custom panic message after the expected value if desired.
+///
+/// # Examples
+///
+/// ```
+/// use pretty_assertions::assert_ok;
+///
+/// let a: Result<u32, String> = Ok(3);
+/// assert_ok!(a);
+/// assert_ok!(a, 3);
+///
+/// assert_ok!(a, 3, "we are testing {:?}", a);
+/// ```
+#[macro_export]
+macro_rules! assert_ok {
+    ($left:expr$(,)?) => ({
+        match &($left) {
+            ::core::result::Result::Ok(_) => {}
+            ::core::result::Result::Err(left_err) => {
+                $crate::private::fail(::core::format_args!("assertion failed: `(left is Ok(_))`\
+                   \n\
+                   \n{}\
+                   \n",
+                   $crate::private::VariantMismatch::new("Ok", "Err", ::core::option::Option::Some(left_err))
+                ))
+            }
+        }
+    });
+    ($left:expr, $right:expr$(,)?) => ({
+        $crate::assert_ok!(@ $left, $right, "", "");
+    });
+    ($left:expr, $right:expr, $($arg:tt)+) => ({
+        $crate::assert_ok!(@ $left, $right, ": ", $($arg)+);
+    });
+    (@ $left:expr, $right:expr, $maybe_colon:expr, $($arg:tt)*) => ({
+        match (&($left), &($right)) {
+            (::core::result::Result::Ok(left_val), right_val) => {
+                if !(*left_val == *right_val) {
+                    use $crate::private::CreateComparison;
+                    $crate::private::fail(::core::format_args!("assertion failed: `(left == Ok(right))`{}{}\
+                       \n\
+                       \n{}\
+                       \n",
+                       $maybe_colon,
+                       format_args!($($arg)*),
+                       (left_val, right_val).create_comparison()
+                    ))
+                }
+            }
+            (::core::result::Result::Err(left_err), _) => {
+                $crate::private::fail(::core::format_args!("assertion failed: `(left == Ok(right))`{}{}\
+                   \n\
+                   \n{}\
+                   \n",
+                   $maybe_colon,
+                   format_args!($($arg)*),
+                   $crate::private::VariantMismatch::new("Ok", "Err", ::core::option::Option::Some(left_err))
+                ))
+            }
+        }
+    });
+}
+
+/// Asserts that a [`Result`] is [`Err`], optionally with an expected inner value (using [`PartialEq`]).
+///
+/// If the result is an [`Ok`], this macro will print the value with its [`Debug`]
+/// representation. If an expected error is given and the result is [`Err`], it will
+/// print a diff of the inner error only, in the same way as [`assert_eq!`].
+///
+/// You can provide a custom panic message after the expected value if desired.
+///
+/// # Examples
+///
+/// ```
+/// use pretty_assertions::assert_err;
+///
+/// let a: Result<u32, String> = Err("oh no".to_string());
+/// assert_err!(a);
+/// assert_err!(a, "oh no");
+///
+/// assert_err!(a, "oh no", "we are testing {:?}", a);
+/// ```
+#[macro_export]
+macro_rules! assert_err {
+    ($left:expr$(,)?) => ({
+        match &($left) {
+            ::core::result::Result::Err(_) => {}
+            ::core::result::Result::Ok(left_val) => {
+                $crate::private::fail(::core::format_args!("assertion failed: `(left is Err(_))`\
+                   \n\
+                   \n{}\
+                   \n",
+                   $crate::private::VariantMismatch::new("Err", "Ok", ::core::option::Option::Some(left_val))
+                ))
+            }
+        }
+    });
+    ($left:expr, $right:expr$(,)?) => ({
+        $crate::assert_err!(@ $left, $right, "", "");
+    });
+    ($left:expr, $right:expr, $($arg:tt)+) => ({
+        $crate::assert_err!(@ $left, $right, ": ", $($arg)+);
+    });
+    (@ $left:expr, $right:expr, $maybe_colon:expr, $($arg:tt)*) => ({
+        match (&($left), &($right)) {
+            (::core::result::Result::Err(left_err), right_err) => {
+                if !(*left_err == *right_err) {
+                    use $crate::private::CreateComparison;
+                    $crate::private::fail(::core::format_args!("assertion failed: `(left == Err(right))`{}{}\
+                       \n\
+                       \n{}\
+                       \n",
+                       $maybe_colon,
+                       format_args!($($arg)*),
+                       (left_err, right_err).create_comparison()
+                    ))
+                }
+            }
+            (::core::result::Result::Ok(left_val), _) => {
+                $crate::private::fail(::core::format_args!("assertion failed: `(left == Err(right))`{}{}\
+                   \n\
+                   \n{}\
+                   \n",
+                   $maybe_colon,
+                   format_args!($($arg)*),
+                   $crate::private::VariantMismatch::new("Err", "Ok", ::core::option::Option::Some(left_val))
+                ))
+            }
+        }
+    });
+}
+
+/// Asserts that an [`Option`] is [`Some`], optionally with an expected inner value (using [`PartialEq`]).
+///
+/// If the option is [`None`], this macro will say so. If an expected value is given and
+/// the option is [`Some`], it will print a diff of the inner value only, in the same
+/// way as [`assert_eq!`].
+///
+/// You can provide a custom panic message after the expected value if desired.
+///
+/// # Examples
+///
+/// ```
+/// use pretty_assertions::assert_some;
+///
+/// let a = Some(3);
+/// assert_some!(a);
+/// assert_some!(a, 3);
+///
+/// assert_some!(a, 3, "we are testing {:?}", a);
+/// ```
+#[macro_export]
+macro_rules! assert_some {
+    ($left:expr$(,)?) => ({
+        match &($left) {
+            ::core::option::Option::Some(_) => {}
+            ::core::option::Option::None => {
+                $crate::private::fail(::core::format_args!("assertion failed: `(left is Some(_))`\
+                   \n\
+                   \n{}\
+                   \n",
+                   $crate::private::VariantMismatch::new("Some", "None", ::core::option::Option::None::<&()>)
+                ))
+            }
+        }
+    });
+    ($left:expr, $right:expr$(,)?) => ({
+        $crate::assert_some!(@ $left, $right, "", "");
+    });
+    ($left:expr, $right:expr, $($arg:tt)+) => ({
+        $crate::assert_some!(@ $left, $right, ": ", $($arg)+);
+    });
+    (@ $left:expr, $right:expr, $maybe_colon:expr, $($arg:tt)*) => ({
+        match (&($left), &($right)) {
+            (::core::option::Option::Some(left_val), right_val) => {
+                if !(*left_val == *right_val) {
+                    use $crate::private::CreateComparison;
+                    $crate::private::fail(::core::format_args!("assertion failed: `(left == Some(right))`{}{}\
+                       \n\
+                       \n{}\
+                       \n",
+                       $maybe_colon,
+                       format_args!($($arg)*),
+                       (left_val, right_val).create_comparison()
+                    ))
+                }
+            }
+            (::core::option::Option::None, _) => {
+                $crate::private::fail(::core::format_args!("assertion failed: `(left == Some(right))`{}{}\
+                   \n\
+                   \n{}\
+                   \n",
+                   $maybe_colon,
+                   format_args!($($arg)*),
+                   $crate::private::VariantMismatch::new("Some", "None", ::core::option::Option::None::<&()>)
+                ))
+            }
+        }
+    });
+}
+
+/// Asserts that an [`Option`] is [`None`].
+///
+/// On panic, this macro will print the inner value with its [`Debug`] representation.
+///
+/// You can provide a custom panic message if desired.
+///
+/// # Examples
+///
+/// ```
+/// use pretty_assertions::assert_none;
+///
+/// let a: Option<u32> = None;
+/// assert_none!(a);
+///
+/// assert_none!(a, "we are testing {:?}", a);
+/// ```
+#[macro_export]
+macro_rules! assert_none {
+    ($left:expr$(,)?) => ({
+        $crate::assert_none!(@ $left, "", "");
+    });
+    ($left:expr, $($arg:tt)+) => ({
+        $crate::assert_none!(@ $left, ": ", $($arg)+);
+    });
+    (@ $left:expr, $maybe_colon:expr, $($arg:tt)*) => ({
+        match &($left) {
+            ::core::option::Option::None => {}
+            ::core::option::Option::Some(left_val) => {
+                $crate::private::fail(::core::format_args!("assertion failed: `(left is None)`{}{}\
+                   \n\
+                   \n{}\
+                   \n",
+                   $maybe_colon,
+                   format_args!($($arg)*),
+                   $crate::private::VariantMismatch::new("None", "Some", ::core::option::Option::Some(left_val))
+                ))
+            }
+        }
+    });
+}
+
+/// Asserts that a value matches a pattern.
+///
+/// On panic, this macro will print a diff derived from [`Debug`] representation of
+/// the value, and a string representation of the pattern.
+///
+/// This is a drop in replacement for [`core::assert_matches::assert_matches!`].
+/// You can provide a custom panic message if desired.
+///
+/// # Examples
+///
+/// ```
+/// use pretty_assertions::assert_matches;
+///
+/// let a = Some(3);
+/// assert_matches!(a, Some(_));
+///
+/// assert_matches!(a, Some(value) if value > 2, "we are testing {:?} with a pattern", a);
+/// ```
+///
+/// # Features
+///
+/// Requires the `unstable` feature to be enabled.
+///
+/// **Please note:** implementation under the `unstable` feature may be changed between
+/// patch versions without warning.
+#[cfg(feature = "unstable")]
+#[macro_export]
+macro_rules! assert_matches {
+    ($left:expr, $( $pattern:pat )|+ $( if $guard: expr )? $(,)?) => ({
+        match $left {
+            $( $pattern )|+ $( if $guard )? => {}
+            ref left_val => {
+                $crate::assert_matches!(
+                    @
+                    left_val,
+                    ::core::stringify!($($pattern)|+ $(if $guard)?),
+                    "",
+                    ""
+                );
+            }
+        }
+    });
+    ($left:expr, $( $pattern:pat )|+ $( if $guard: expr )?, $($arg:tt)+) => ({
+        match $left {
             $( $pattern )|+ $( if $guard )? => {}
             ref left_val => {
                 $crate::assert_matches!(
@@ -409,30 +1911,405 @@ macro_rules! assert_matches {
                     }
                 }
 
-                ::core::panic!("assertion failed: `(left matches right)`{}{}\
+                $crate::private::fail(::core::format_args!("assertion failed: `(left matches right)`{}{}\
                    \n\
                    \n{}\
                    \n",
                    $maybe_colon,
                    format_args!($($arg)*),
                    $crate::Comparison::new(left_val, &Pattern(right_val))
-                )
+                ))
+            }
+        }
+    });
+}
+
+/// Asserts that a string matches a regular expression.
+///
+/// The pattern may be given as a `&str`, a `String` or a compiled
+/// [`regex::Regex`](::regex::Regex).
+///
+/// The pattern must match somewhere in the string, as by
+/// [`Regex::is_match`](::regex::Regex::is_match). `^` and `$` match only at the start and
+/// end of the string, unless multi-line mode is enabled with `(?m)`.
+///
+/// On panic, this macro will print the string without [`Debug`] formatting.
+/// For patterns anchored to the start and end of a line, each line which does not match
+/// the pattern on its own is highlighted.
+/// See [`RegexComparison`] for further details.
+///
+/// You can provide a custom panic message if desired.
+///
+/// # Examples
+///
+/// ```
+/// use pretty_assertions::assert_matches_regex;
+///
+/// let log = "1 INFO started\n2 INFO stopped";
+/// assert_matches_regex!(log, r"^(\d+ INFO \w+\n?)+$");
+/// assert_matches_regex!(log, r"(?m)^2 INFO stopped$");
+///
+/// assert_matches_regex!(log, r"stopped$", "we are testing the log {}", log);
+/// ```
+///
+/// # Features
+///
+/// Requires the `regex` feature to be enabled.
+#[cfg(feature = "regex")]
+#[macro_export]
+macro_rules! assert_matches_regex {
+    ($text:expr, $pattern:expr$(,)?) => ({
+        $crate::assert_matches_regex!(@ $text, $pattern, "", "");
+    });
+    ($text:expr, $pattern:expr, $($arg:tt)*) => ({
+        $crate::assert_matches_regex!(@ $text, $pattern, ": ", $($arg)+);
+    });
+    (@ $text:expr, $pattern:expr, $maybe_colon:expr, $($arg:tt)*) => ({
+        match (&($text), &($pattern)) {
+            (text_val, pattern_val) => {
+                let regex = $crate::private::AsRegex::as_regex(pattern_val);
+                let comparison = $crate::RegexComparison::new(text_val, &*regex);
+                if !comparison.is_match() {
+                    $crate::private::fail(::core::format_args!("assertion failed: `(text matches pattern)`{}{}\
+                       \n\
+                       \n{}\
+                       \n",
+                       $maybe_colon,
+                       format_args!($($arg)*),
+                       comparison
+                    ))
+                }
             }
         }
     });
 }
 
+/// Asserts that two expressions are equal to each other (using [`PartialEq`]), in debug builds only.
+///
+/// This is equivalent to [`assert_eq!`], but is only enabled in builds with
+/// `debug_assertions` enabled, in the same way as [`core::debug_assert!`].
+/// Otherwise, the expressions are not evaluated.
+///
+/// This is a drop in replacement for [`core::debug_assert_eq!`].
+///
+/// # Examples
+///
+/// ```
+/// use pretty_assertions::debug_assert_eq;
+///
+/// let a = 3;
+/// let b = 1 + 2;
+/// debug_assert_eq!(a, b);
+/// ```
+#[macro_export]
+macro_rules! debug_assert_eq {
+    ($($arg:tt)*) => ({
+        if ::core::cfg!(debug_assertions) {
+            $crate::assert_eq!($($arg)*);
+        }
+    });
+}
+
+/// Asserts that two expressions are equal to each other (using [`PartialEq`]), in debug builds only.
+///
+/// This is equivalent to [`assert_str_eq!`], but is only enabled in builds with
+/// `debug_assertions` enabled, in the same way as [`core::debug_assert!`].
+/// Otherwise, the expressions are not evaluated.
+///
+/// # Examples
+///
+/// ```
+/// use pretty_assertions::debug_assert_str_eq;
+///
+/// let a = "foo\nbar";
+/// let b = ["foo", "bar"].join("\n");
+/// debug_assert_str_eq!(a, b);
+/// ```
+#[macro_export]
+macro_rules! debug_assert_str_eq {
+    ($($arg:tt)*) => ({
+        if ::core::cfg!(debug_assertions) {
+            $crate::assert_str_eq!($($arg)*);
+        }
+    });
+}
+
+/// Asserts that two expressions are not equal to each other (using [`PartialEq`]), in debug builds only.
+///
+/// This is equivalent to [`assert_ne!`], but is only enabled in builds with
+/// `debug_assertions` enabled, in the same way as [`core::debug_assert!`].
+/// Otherwise, the expressions are not evaluated.
+///
+/// This is a drop in replacement for [`core::debug_assert_ne!`].
+///
+/// # Examples
+///
+/// ```
+/// use pretty_assertions::debug_assert_ne;
+///
+/// let a = 3;
+/// let b = 2;
+/// debug_assert_ne!(a, b);
+/// ```
+#[macro_export]
+macro_rules! debug_assert_ne {
+    ($($arg:tt)*) => ({
+        if ::core::cfg!(debug_assertions) {
+            $crate::assert_ne!($($arg)*);
+        }
+    });
+}
+
+/// Asserts that a [`Result`] is [`Ok`], in debug builds only.
+///
+/// This is equivalent to [`assert_ok!`], but is only enabled in builds with
+/// `debug_assertions` enabled, in the same way as [`core::debug_assert!`].
+/// Otherwise, the expressions are not evaluated.
+///
+/// # Examples
+///
+/// ```
+/// use pretty_assertions::debug_assert_ok;
+///
+/// let a: Result<u32, String> = Ok(3);
+/// debug_assert_ok!(a, 3);
+/// ```
+#[macro_export]
+macro_rules! debug_assert_ok {
+    ($($arg:tt)*) => ({
+        if ::core::cfg!(debug_assertions) {
+            $crate::assert_ok!($($arg)*);
+        }
+    });
+}
+
+/// Asserts that a [`Result`] is [`Err`], in debug builds only.
+///
+/// This is equivalent to [`assert_err!`], but is only enabled in builds with
+/// `debug_assertions` enabled, in the same way as [`core::debug_assert!`].
+/// Otherwise, the expressions are not evaluated.
+///
+/// # Examples
+///
+/// ```
+/// use pretty_assertions::debug_assert_err;
+///
+/// let a: Result<u32, String> = Err("oh no".to_string());
+/// debug_assert_err!(a, "oh no");
+/// ```
+#[macro_export]
+macro_rules! debug_assert_err {
+    ($($arg:tt)*) => ({
+        if ::core::cfg!(debug_assertions) {
+            $crate::assert_err!($($arg)*);
+        }
+    });
+}
+
+/// Asserts that an [`Option`] is [`Some`], in debug builds only.
+///
+/// This is equivalent to [`assert_some!`], but is only enabled in builds with
+/// `debug_assertions` enabled, in the same way as [`core::debug_assert!`].
+/// Otherwise, the expressions are not evaluated.
+///
+/// # Examples
+///
+/// ```
+/// use pretty_assertions::debug_assert_some;
+///
+/// let a = Some(3);
+/// debug_assert_some!(a, 3);
+/// ```
+#[macro_export]
+macro_rules! debug_assert_some {
+    ($($arg:tt)*) => ({
+        if ::core::cfg!(debug_assertions) {
+            $crate::assert_some!($($arg)*);
+        }
+    });
+}
+
+/// Asserts that an [`Option`] is [`None`], in debug builds only.
+///
+/// This is equivalent to [`assert_none!`], but is only enabled in builds with
+/// `debug_assertions` enabled, in the same way as [`core::debug_assert!`].
+/// Otherwise, the expressions are not evaluated.
+///
+/// # Examples
+///
+/// ```
+/// use pretty_assertions::debug_assert_none;
+///
+/// let a: Option<u32> = None;
+/// debug_assert_none!(a);
+/// ```
+#[macro_export]
+macro_rules! debug_assert_none {
+    ($($arg:tt)*) => ({
+        if ::core::cfg!(debug_assertions) {
+            $crate::assert_none!($($arg)*);
+        }
+    });
+}
+
+/// Asserts that a value matches a pattern, in debug builds only.
+///
+/// This is equivalent to [`assert_matches!`], but is only enabled in builds with
+/// `debug_assertions` enabled, in the same way as [`core::debug_assert!`].
+/// Otherwise, the expressions are not evaluated.
+///
+/// This is a drop in replacement for [`core::assert_matches::debug_assert_matches!`].
+///
+/// # Examples
+///
+/// ```
+/// use pretty_assertions::debug_assert_matches;
+///
+/// let a = Some(3);
+/// debug_assert_matches!(a, Some(_));
+/// ```
+///
+/// # Features
+///
+/// Requires the `unstable` feature to be enabled.
+///
+/// **Please note:** implementation under the `unstable` feature may be changed between
+/// patch versions without warning.
+#[cfg(feature = "unstable")]
+#[macro_export]
+macro_rules! debug_assert_matches {
+    ($($arg:tt)*) => ({
+        if ::core::cfg!(debug_assertions) {
+            $crate::assert_matches!($($arg)*);
+        }
+    });
+}
+
+/// Asserts that a string matches a regular expression, in debug builds only.
+///
+/// This is equivalent to [`assert_matches_regex!`], but is only enabled in builds with
+/// `debug_assertions` enabled, in the same way as [`core::debug_assert!`].
+/// Otherwise, the expressions are not evaluated.
+///
+/// # Examples
+///
+/// ```
+/// use pretty_assertions::debug_assert_matches_regex;
+///
+/// let log = "1 INFO started";
+/// debug_assert_matches_regex!(log, r"^\d+ INFO \w+$");
+/// ```
+///
+/// # Features
+///
+/// Requires the `regex` feature to be enabled.
+#[cfg(feature = "regex")]
+#[macro_export]
+macro_rules! debug_assert_matches_regex {
+    ($($arg:tt)*) => ({
+        if ::core::cfg!(debug_assertions) {
+            $crate::assert_matches_regex!($($arg)*);
+        }
+    });
+}
+
 // Not public API. Used by the expansion of this crate's assert macros.
 #[doc(hidden)]
 pub mod private {
+    #[cfg(feature = "std")]
+    pub use crate::file::{assert_file, FileMismatch};
+    #[cfg(feature = "std")]
+    pub use crate::inline::update_literal;
+    pub use crate::iter::{iter_eq, IterMismatch};
+    #[cfg(feature = "std")]
+    pub use crate::snapshot::{assert_snapshot, SnapshotMismatch};
+
+    #[cfg(all(feature = "alloc", not(feature = "std"), feature = "regex"))]
+    use alloc::borrow::Cow;
     #[cfg(feature = "alloc")]
     use alloc::string::String;
+    #[cfg(all(feature = "std", feature = "regex"))]
+    use std::borrow::Cow;
 
     pub trait CompareAsStrByDefault: AsRef<str> {}
     impl CompareAsStrByDefault for str {}
     impl CompareAsStrByDefault for String {}
     impl<T: CompareAsStrByDefault + ?Sized> CompareAsStrByDefault for &T {}
 
+    /// Describe an enum value which was not of the expected variant.
+    pub struct VariantMismatch<'a, T: ?Sized> {
+        expected: &'static str,
+        found: &'static str,
+        value: Option<&'a T>,
+    }
+
+    impl<'a, T: ?Sized> VariantMismatch<'a, T> {
+        pub fn new(expected: &'static str, found: &'static str, value: Option<&'a T>) -> Self {
+            VariantMismatch {
+                expected,
+                found,
+                value,
+            }
+        }
+    }
+
+    impl<T: core::fmt::Debug + ?Sized> core::fmt::Display for VariantMismatch<'_, T> {
+        fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            match self.value {
+                Some(value) => {
+                    writeln!(f, "Expected {}, found {}:", self.expected, self.found)?;
+                    writeln!(f, "{:#?}", value)
+                }
+                None => writeln!(f, "Expected {}, found {}", self.expected, self.found),
+            }
+        }
+    }
+
+    /// Literals can only be updated with access to the file system.
+    #[cfg(not(feature = "std"))]
+    pub fn update_literal(_: Option<&str>, _: &str, _: u32, _: u32, _: &str, _: &str) -> bool {
+        false
+    }
+
+    /// The text stored in a snapshot of a value.
+    pub trait SnapshotText {
+        fn snapshot_text(self) -> String;
+    }
+
+    impl<T: core::fmt::Debug> SnapshotText for &(T,) {
+        fn snapshot_text(self) -> String {
+            format!("{:#?}", self.0)
+        }
+    }
+
+    impl<T: CompareAsStrByDefault + ?Sized> SnapshotText for (&T,) {
+        fn snapshot_text(self) -> String {
+            String::from(self.0.as_ref())
+        }
+    }
+
+    /// Panic with the message of a failed assertion.
+    ///
+    /// Environment variables are only read here, so that they affect the output of failed
+    /// assertions, but not comparisons which are formatted directly. They select the format
+    /// of diffs, and whether the text diffed is also written to files. Under GitHub Actions,
+    /// the call site is also annotated with the failure.
+    #[track_caller]
+    pub fn fail(message: core::fmt::Arguments) -> ! {
+        #[cfg(feature = "std")]
+        panic!(
+            "{}",
+            crate::artifacts::failure_message(message, core::panic::Location::caller())
+        );
+        #[cfg(not(feature = "std"))]
+        panic!("{}", message)
+    }
+
+    /// The type name of a value, used to find the path of the function a macro expands in.
+    pub fn type_name_of<T>(_: T) -> &'static str {
+        core::any::type_name::<T>()
+    }
+
     pub trait CreateComparison {
         type Comparison;
         fn create_comparison(self) -> Self::Comparison;
@@ -455,4 +2332,40 @@ pub mod private {
             crate::StrComparison::new(self.0, self.1)
         }
     }
+
+    #[cfg(feature = "regex")]
+    pub trait AsRegex {
+        fn as_regex(&self) -> Cow<'_, regex::Regex>;
+    }
+
+    #[cfg(feature = "regex")]
+    impl AsRegex for str {
+        fn as_regex(&self) -> Cow<'_, regex::Regex> {
+            match regex::Regex::new(self) {
+                Ok(regex) => Cow::Owned(regex),
+                Err(err) => panic!("assertion failed: invalid pattern\n\n{}\n", err),
+            }
+        }
+    }
+
+    #[cfg(feature = "regex")]
+    impl AsRegex for String {
+        fn as_regex(&self) -> Cow<'_, regex::Regex> {
+            self.as_str().as_regex()
+        }
+    }
+
+    #[cfg(feature = "regex")]
+    impl AsRegex for regex::Regex {
+        fn as_regex(&self) -> Cow<'_, regex::Regex> {
+            Cow::Borrowed(self)
+        }
+    }
+
+    #[cfg(feature = "regex")]
+    impl<T: AsRegex + ?Sized> AsRegex for &T {
+        fn as_regex(&self) -> Cow<'_, regex::Regex> {
+            (**self).as_regex()
+        }
+    }
 }
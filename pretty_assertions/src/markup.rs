@@ -0,0 +1,338 @@
+use crate::{printer, StrComparison};
+#[cfg(feature = "alloc")]
+use alloc::{format, string::String, vec::Vec};
+use core::fmt::{self, Display};
+
+/// The number of spaces added for each level of nesting.
+const INDENT: usize = 2;
+
+/// A node of a parsed document, normalized so that insignificant differences are removed.
+///
+/// Attributes are sorted by name, runs of whitespace in text are collapsed to a single
+/// space, and text consisting only of whitespace is dropped. Comments are not kept.
+#[derive(Debug, PartialEq)]
+pub(crate) enum Node {
+    Element(Element),
+    Text(String),
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct Element {
+    pub(crate) name: String,
+    pub(crate) attributes: Vec<(String, Option<String>)>,
+    pub(crate) children: Vec<Node>,
+}
+
+impl Element {
+    /// Build an element, sorting its attributes by name.
+    pub(crate) fn new<T>(name: String, attributes: T, children: Vec<Node>) -> Element
+    where
+        T: IntoIterator<Item = (String, Option<String>)>,
+    {
+        let mut attributes: Vec<(String, Option<String>)> = attributes.into_iter().collect();
+        attributes.sort();
+        Element {
+            name,
+            attributes,
+            children,
+        }
+    }
+}
+
+/// Normalize text, collapsing each run of whitespace into a single space.
+///
+/// Returns `None` if the text is only whitespace.
+pub(crate) fn text_node(text: &str) -> Option<Node> {
+    let text = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+    if text.is_empty() {
+        None
+    } else {
+        Some(Node::Text(text))
+    }
+}
+
+/// HTML elements which never have any content, nor a closing tag.
+#[cfg(feature = "tl")]
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// The language a document is written in, which affects how elements are printed.
+#[derive(Clone, Copy)]
+pub(crate) enum Markup {
+    #[cfg(feature = "tl")]
+    Html,
+    #[cfg(feature = "roxmltree")]
+    Xml,
+}
+
+impl Markup {
+    fn name(self) -> &'static str {
+        match self {
+            #[cfg(feature = "tl")]
+            Markup::Html => "HTML",
+            #[cfg(feature = "roxmltree")]
+            Markup::Xml => "XML",
+        }
+    }
+
+    /// If the element has no closing tag, the end of its opening tag.
+    fn void_end(self, element: &Element) -> Option<&'static str> {
+        match self {
+            #[cfg(feature = "tl")]
+            Markup::Html if VOID_ELEMENTS.contains(&element.name.as_str()) => Some(">"),
+            #[cfg(feature = "roxmltree")]
+            Markup::Xml if element.children.is_empty() => Some("/>"),
+            _ => None,
+        }
+    }
+}
+
+/// Pretty-print nodes, with each element and run of text on its own line.
+///
+/// An element containing only text is printed on a single line.
+fn write_nodes(output: &mut String, nodes: &[Node], markup: Markup, indent: usize) {
+    for node in nodes {
+        output.push_str(&" ".repeat(indent));
+        match node {
+            Node::Text(text) => output.push_str(text),
+            Node::Element(element) => {
+                output.push('<');
+                output.push_str(&element.name);
+                for (name, value) in &element.attributes {
+                    output.push(' ');
+                    output.push_str(name);
+                    if let Some(value) = value {
+                        output.push_str(&format!("={:?}", value));
+                    }
+                }
+                if let Some(end) = markup.void_end(element) {
+                    output.push_str(end);
+                    output.push('\n');
+                    continue;
+                }
+                output.push('>');
+                match element.children.as_slice() {
+                    [] => (),
+                    [Node::Text(text)] => output.push_str(text),
+                    children => {
+                        output.push('\n');
+                        write_nodes(output, children, markup, indent + INDENT);
+                        output.push_str(&" ".repeat(indent));
+                    }
+                }
+                output.push_str("</");
+                output.push_str(&element.name);
+                output.push('>');
+            }
+        }
+        output.push('\n');
+    }
+}
+
+/// A difference between two documents, and the path of the element where it was found.
+struct Mismatch {
+    path: String,
+    reason: &'static str,
+}
+
+/// Collect the paths of elements which differ between two lists of sibling nodes.
+///
+/// Siblings are compared by position. Where both nodes are elements with the same name,
+/// the comparison continues into their children, so that only the innermost elements
+/// which differ are reported.
+fn mismatches(path: &str, left: &[Node], right: &[Node], output: &mut Vec<Mismatch>) {
+    for index in 0..left.len().max(right.len()) {
+        let (left_node, right_node) = (left.get(index), right.get(index));
+        // Number the element among the siblings on whichever side it is present
+        let siblings = if left_node.is_some() { left } else { right };
+        let node_path = match left_node.or(right_node) {
+            Some(Node::Element(element)) => element_path(path, siblings, index, &element.name),
+            _ => format!("{}/text()", path),
+        };
+        let reason = match (left_node, right_node) {
+            (Some(Node::Element(l)), Some(Node::Element(r))) if l.name == r.name => {
+                if l.attributes != r.attributes {
+                    output.push(Mismatch {
+                        path: node_path.clone(),
+                        reason: "attributes differ",
+                    });
+                }
+                mismatches(&node_path, &l.children, &r.children, output);
+                continue;
+            }
+            (Some(l), Some(r)) if l == r => continue,
+            (Some(Node::Text(_)), Some(Node::Text(_))) => "text differs",
+            (Some(_), Some(_)) => "nodes differ",
+            (Some(_), None) => "only on left",
+            (None, _) => "only on right",
+        };
+        output.push(Mismatch {
+            path: node_path,
+            reason,
+        });
+    }
+}
+
+/// The XPath-style path of a child element, such as `/div/ul/li[2]`.
+///
+/// The element is numbered among its siblings of the same name, if there are several.
+fn element_path(parent: &str, siblings: &[Node], index: usize, name: &str) -> String {
+    let is_named = |node: &Node| matches!(node, Node::Element(element) if element.name == name);
+    let position = siblings
+        .iter()
+        .take(index)
+        .filter(|node| is_named(node))
+        .count()
+        + 1;
+    if siblings.iter().filter(|node| is_named(node)).count() > 1 {
+        format!("{}/{}[{}]", parent, name, position)
+    } else {
+        format!("{}/{}", parent, name)
+    }
+}
+
+/// A document in a markup language, and the result of parsing it.
+pub(crate) struct Document<'a> {
+    pub(crate) text: &'a str,
+    pub(crate) nodes: Result<Vec<Node>, String>,
+}
+
+/// A comparison of two documents in the same markup language.
+///
+/// Shared by the comparisons of each language, which only differ in how they parse text.
+pub(crate) struct MarkupComparison<'a> {
+    pub(crate) markup: Markup,
+    pub(crate) left: Document<'a>,
+    pub(crate) right: Document<'a>,
+}
+
+impl MarkupComparison<'_> {
+    /// Whether the documents are the same once normalized.
+    ///
+    /// If either document fails to parse, the text of each is compared instead.
+    pub(crate) fn is_equal(&self) -> bool {
+        match (&self.left.nodes, &self.right.nodes) {
+            (Ok(left), Ok(right)) => left == right,
+            _ => self.left.text == self.right.text,
+        }
+    }
+}
+
+impl Display for MarkupComparison<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (left, right) = match (&self.left.nodes, &self.right.nodes) {
+            (Ok(left), Ok(right)) => (left, right),
+            (left, right) => {
+                for (side, nodes) in &[("left", left), ("right", right)] {
+                    if let Err(err) = nodes {
+                        let name = self.markup.name();
+                        writeln!(f, "Failed to parse {} as {}: {}", side, name, err)?;
+                    }
+                }
+                writeln!(f)?;
+                return Display::fmt(&StrComparison::new(self.left.text, self.right.text), f);
+            }
+        };
+
+        let mut found = Vec::new();
+        mismatches("", left, right, &mut found);
+        if !found.is_empty() {
+            writeln!(f, "Mismatched elements:")?;
+            for mismatch in found {
+                writeln!(f, "  {}: {}", mismatch.path, mismatch.reason)?;
+            }
+            writeln!(f)?;
+        }
+
+        let (mut left_text, mut right_text) = (String::new(), String::new());
+        write_nodes(&mut left_text, left, self.markup, 0);
+        write_nodes(&mut right_text, right, self.markup, 0);
+        printer::write_header(f)?;
+        printer::write_lines(f, left_text.trim_end(), right_text.trim_end())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn element(name: &str, attributes: &[(&str, &str)], children: Vec<Node>) -> Node {
+        Node::Element(Element::new(
+            name.to_owned(),
+            attributes
+                .iter()
+                .map(|(name, value)| (name.to_string(), Some(value.to_string()))),
+            children,
+        ))
+    }
+
+    fn text(text: &str) -> Node {
+        text_node(text).unwrap()
+    }
+
+    #[test]
+    fn text_node_collapses_whitespace() {
+        assert_eq!(
+            text_node("\n  Hello \t world\n"),
+            Some(Node::Text("Hello world".into()))
+        );
+        assert_eq!(text_node(" \n "), None);
+    }
+
+    #[test]
+    #[cfg(feature = "tl")]
+    fn write_nodes_nested() {
+        let nodes = vec![element(
+            "div",
+            &[("id", "a"), ("class", "card")],
+            vec![
+                element("p", &[], vec![text("Hello")]),
+                element("br", &[], vec![]),
+                element("p", &[], vec![]),
+            ],
+        )];
+        let mut output = String::new();
+        write_nodes(&mut output, &nodes, Markup::Html, 0);
+        assert_eq!(
+            output,
+            "<div class=\"card\" id=\"a\">\n  <p>Hello</p>\n  <br>\n  <p></p>\n</div>\n"
+        );
+    }
+
+    #[test]
+    fn mismatches_paths() {
+        let left = vec![element(
+            "ul",
+            &[],
+            vec![
+                element("li", &[("class", "a")], vec![text("one")]),
+                element("li", &[], vec![text("two")]),
+            ],
+        )];
+        let right = vec![element(
+            "ul",
+            &[],
+            vec![
+                element("li", &[("class", "b")], vec![text("one")]),
+                element("li", &[], vec![text("three")]),
+                element("li", &[], vec![text("four")]),
+            ],
+        )];
+        let mut found = Vec::new();
+        mismatches("", &left, &right, &mut found);
+        let found: Vec<(String, &str)> = found
+            .into_iter()
+            .map(|mismatch| (mismatch.path, mismatch.reason))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("/ul/li[1]".to_string(), "attributes differ"),
+                ("/ul/li[2]/text()".to_string(), "text differs"),
+                ("/ul/li[3]".to_string(), "only on right"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use crate::printer;
+use crate::render::Renderer;
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+
+/// A diff between two texts, as data rather than formatted output.
+///
+/// This describes the same changes as the printed diffs, for use by tools such as test
+/// dashboards. It is computed from two strings by [`Diff::new`], or produced by
+/// [`Comparison::diff`](crate::Comparison::diff) and
+/// [`StrComparison::diff`](crate::StrComparison::diff). A [`Renderer`] presents it as text.
+///
+/// Changed lines are grouped into hunks, each with up to three unchanged lines of context
+/// around the changes, in the same way as a unified diff. If the texts are equal, there
+/// are no hunks.
+///
+/// ```
+/// use pretty_assertions::{LineKind, Span, StrComparison};
+///
+/// let diff = StrComparison::new("foo\nbar\n", "foo\nbaz\n").diff();
+/// let hunk = &diff.hunks[0];
+/// assert_eq!((hunk.left_start, hunk.left_count), (1, 2));
+///
+/// let deleted = &hunk.lines[1];
+/// assert_eq!(deleted.kind, LineKind::Delete);
+/// assert_eq!(deleted.text, "bar");
+/// assert_eq!(deleted.spans, [Span { start: 2, end: 3 }]);
+/// ```
+///
+/// # Features
+///
+/// With the `serde` feature enabled, each type in the model implements `Serialize`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize))]
+pub struct Diff {
+    /// The label of the left text, shown in the header of a diff.
+    pub left_label: String,
+    /// The label of the right text, shown in the header of a diff.
+    pub right_label: String,
+    /// The groups of changed lines, in order.
+    pub hunks: Vec<Hunk>,
+}
+
+impl Diff {
+    /// Diff two multiline strings, with three lines of context around each change.
+    ///
+    /// Text is split into lines as a file is split by `diff`, so a newline at the end of
+    /// the text does not start another line.
+    pub fn new(left: &str, right: &str) -> Diff {
+        Diff::with_context(left, right, printer::UNIFIED_CONTEXT)
+    }
+
+    /// Diff two multiline strings, with the given number of lines of context around each
+    /// change.
+    ///
+    /// With a context of `usize::MAX`, every line is shown in a single hunk as long as the
+    /// texts differ.
+    pub fn with_context(left: &str, right: &str, context: usize) -> Diff {
+        printer::diff_model(left, right, context)
+    }
+
+    /// Render the diff to a string.
+    ///
+    /// ```
+    /// use pretty_assertions::{Diff, UnifiedRenderer};
+    ///
+    /// let diff = Diff::new("foo\nbar\n", "foo\nbaz\n");
+    /// assert_eq!(
+    ///     diff.render(&UnifiedRenderer),
+    ///     "--- left\n+++ right\n@@ -1,2 +1,2 @@\n foo\n-bar\n+baz\n"
+    /// );
+    /// ```
+    pub fn render<TRenderer: Renderer + ?Sized>(&self, renderer: &TRenderer) -> String {
+        let mut output = String::new();
+        renderer
+            .render(&mut output, self)
+            .expect("writing to a string cannot fail");
+        output
+    }
+}
+
+/// A group of changed lines, along with the unchanged lines around them.
+///
+/// Line numbers are counted from 1. As in a unified diff, a hunk with no lines on one
+/// side starts at the number of the line before it on that side.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize))]
+pub struct Hunk {
+    /// The number of the first line of the hunk in the left text.
+    pub left_start: usize,
+    /// The number of lines of the hunk in the left text.
+    pub left_count: usize,
+    /// The number of the first line of the hunk in the right text.
+    pub right_start: usize,
+    /// The number of lines of the hunk in the right text.
+    pub right_count: usize,
+    /// The lines of the hunk, in order.
+    pub lines: Vec<Line>,
+}
+
+/// A single line of a diff.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize))]
+pub struct Line {
+    /// Whether the line is in both texts, or only one of them.
+    pub kind: LineKind,
+    /// The text of the line, without its newline.
+    pub text: String,
+    /// The number of the line in the left text, if it is there.
+    pub left_line: Option<usize>,
+    /// The number of the line in the right text, if it is there.
+    pub right_line: Option<usize>,
+    /// The parts of the line which differ, where a single line is replaced by another.
+    ///
+    /// Empty for unchanged lines, and for changed lines which were not compared with
+    /// another line.
+    pub spans: Vec<Span>,
+    /// Whether this is the last line of its text, and is not followed by a newline.
+    pub missing_newline: bool,
+}
+
+/// How a line differs between the two texts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum LineKind {
+    /// The line is in both texts.
+    Equal,
+    /// The line is only in the left text.
+    Delete,
+    /// The line is only in the right text.
+    Insert,
+}
+
+/// A range of changed text within a line, as byte offsets into [`Line::text`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize))]
+pub struct Span {
+    /// The offset of the first changed byte.
+    pub start: usize,
+    /// The offset after the last changed byte.
+    pub end: usize,
+}
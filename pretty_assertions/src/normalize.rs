@@ -0,0 +1,149 @@
+use ::regex::Regex;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{borrow::Cow, sync::RwLock};
+
+/// A rule which rewrites text before it is diffed, to hide noise such as memory addresses.
+///
+/// Every match of the regular expression is replaced, using the same syntax as
+/// [`Regex::replace_all`], so the replacement may refer to capture groups such as `$1`.
+///
+/// Normalizers only affect how a diff is rendered, not whether values are considered
+/// equal. They are applied to the `{:#?}` representations shown by
+/// [`Comparison`](crate::Comparison), and to the text shown by
+/// [`StrComparison`](crate::StrComparison). Use [`Redaction`](crate::Redaction) instead to
+/// ignore part of a value when checking equality.
+///
+/// Normalizers can be added to a single comparison, using [`Comparison::normalize`] or
+/// [`StrComparison::normalize`], or to every comparison, using [`add_global_normalizer`].
+///
+/// ```
+/// use pretty_assertions::{Normalizer, StrComparison};
+/// use regex::Regex;
+///
+/// let address = Normalizer::new(Regex::new("0x[0-9a-f]+").unwrap(), "0xADDR");
+/// let comparison = StrComparison::new("ptr: 0x7ffd01, len: 2", "ptr: 0x7ffe9a, len: 3")
+///     .normalize(address);
+/// // Only the length is shown as changed
+/// print!("{}", comparison);
+/// ```
+///
+/// [`Comparison::normalize`]: crate::Comparison::normalize
+/// [`StrComparison::normalize`]: crate::StrComparison::normalize
+///
+/// # Features
+///
+/// Requires the `regex` feature to be enabled.
+#[derive(Clone, Debug)]
+pub struct Normalizer {
+    regex: Regex,
+    replacement: String,
+}
+
+impl Normalizer {
+    /// Replace every match of `regex` with `replacement`.
+    pub fn new<T: Into<String>>(regex: Regex, replacement: T) -> Normalizer {
+        Normalizer {
+            regex,
+            replacement: replacement.into(),
+        }
+    }
+}
+
+/// The normalizers applied to every comparison.
+#[cfg(feature = "std")]
+static GLOBAL_NORMALIZERS: RwLock<Vec<Normalizer>> = RwLock::new(Vec::new());
+
+/// Apply a normalizer to every comparison rendered from now on, on any thread.
+///
+/// Global normalizers are applied before those added to a single comparison.
+/// As tests run in parallel, this is best called before any assertions are made.
+///
+/// ```
+/// use pretty_assertions::{add_global_normalizer, Normalizer};
+/// use regex::Regex;
+///
+/// let temp_dir = std::env::temp_dir().display().to_string();
+/// add_global_normalizer(Normalizer::new(Regex::new(&regex::escape(&temp_dir)).unwrap(), "$$TMP"));
+/// ```
+///
+/// # Features
+///
+/// Requires the `regex` and `std` features to be enabled.
+#[cfg(feature = "std")]
+pub fn add_global_normalizer(normalizer: Normalizer) {
+    // A panic while holding the lock cannot leave the list in an invalid state
+    let mut normalizers = GLOBAL_NORMALIZERS
+        .write()
+        .unwrap_or_else(|err| err.into_inner());
+    normalizers.push(normalizer);
+}
+
+/// Remove every normalizer added by [`add_global_normalizer`].
+///
+/// # Features
+///
+/// Requires the `regex` and `std` features to be enabled.
+#[cfg(feature = "std")]
+pub fn clear_global_normalizers() {
+    let mut normalizers = GLOBAL_NORMALIZERS
+        .write()
+        .unwrap_or_else(|err| err.into_inner());
+    normalizers.clear();
+}
+
+/// Apply the global normalizers, then the given normalizers, to the text.
+pub(crate) fn normalize<'a>(text: &'a str, normalizers: &[Normalizer]) -> Cow<'a, str> {
+    let mut text = Cow::Borrowed(text);
+    #[cfg(feature = "std")]
+    {
+        let global = GLOBAL_NORMALIZERS
+            .read()
+            .unwrap_or_else(|err| err.into_inner());
+        text = apply(text, &global);
+    }
+    apply(text, normalizers)
+}
+
+fn apply<'a>(mut text: Cow<'a, str>, normalizers: &[Normalizer]) -> Cow<'a, str> {
+    for normalizer in normalizers {
+        let replaced = match normalizer
+            .regex
+            .replace_all(&text, normalizer.replacement.as_str())
+        {
+            Cow::Owned(replaced) => replaced,
+            Cow::Borrowed(_) => continue,
+        };
+        text = Cow::Owned(replaced);
+    }
+    text
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn apply_in_order() {
+        let normalizers = [
+            Normalizer::new(Regex::new("0x[0-9a-f]+").unwrap(), "0xADDR"),
+            Normalizer::new(Regex::new(r"(\w+)=0xADDR").unwrap(), "$1=?"),
+        ];
+        assert_eq!(
+            apply(Cow::Borrowed("a=0x1f, b=2"), &normalizers),
+            "a=?, b=2"
+        );
+    }
+
+    #[test]
+    fn apply_unchanged() {
+        let normalizers = [Normalizer::new(Regex::new("x").unwrap(), "y")];
+        assert!(matches!(
+            apply(Cow::Borrowed("abc"), &normalizers),
+            Cow::Borrowed("abc")
+        ));
+    }
+}
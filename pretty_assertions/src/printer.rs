@@ -1,7 +1,8 @@
+use crate::model::{Diff, Hunk, Line, LineKind, Span};
 #[cfg(feature = "alloc")]
-use alloc::format;
+use alloc::{format, string::String, vec::Vec};
 use core::fmt;
-use yansi::Color::{Green, Red};
+use yansi::Color::{Cyan, Green, Red};
 use yansi::{Paint, Style};
 
 macro_rules! paint {
@@ -13,8 +14,59 @@ macro_rules! paint {
 const SIGN_RIGHT: char = '>'; // + > →
 const SIGN_LEFT: char = '<'; // - < ←
 
+/// The environment variable which selects the format of diffs in assertion failures.
+#[cfg(feature = "std")]
+pub(crate) const FORMAT_VAR: &str = "PRETTY_ASSERTIONS_FORMAT";
+
+/// The format in which diffs are presented.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum Format {
+    /// Colorised, with changes within each line highlighted. The default.
+    Pretty,
+    /// A plain unified diff, as produced by `diff -u`.
+    #[cfg(feature = "std")]
+    Unified,
+    /// A unified diff in a fenced Markdown code block, for pull request comments.
+    #[cfg(feature = "std")]
+    Markdown,
+    /// The data model of the diff, as JSON.
+    #[cfg(all(feature = "std", feature = "serde_json"))]
+    Json,
+}
+
+/// The format selected by the `PRETTY_ASSERTIONS_FORMAT` environment variable.
+///
+/// Unknown formats fall back to the default. The `json` format also requires the
+/// `serde_json` feature.
+#[cfg(feature = "std")]
+pub(crate) fn selected_format() -> Format {
+    match std::env::var(FORMAT_VAR).as_deref() {
+        Ok("unified") => Format::Unified,
+        Ok("markdown") => Format::Markdown,
+        #[cfg(feature = "serde_json")]
+        Ok("json") => Format::Json,
+        _ => Format::Pretty,
+    }
+}
+
+/// The format in which diffs are presented.
+///
+/// This is only selected by the environment while the message of a failed assertion is
+/// rendered. Otherwise, and without the `std` feature, it is always the default.
+fn format() -> Format {
+    #[cfg(feature = "std")]
+    return crate::artifacts::format();
+    #[cfg(not(feature = "std"))]
+    Format::Pretty
+}
+
 /// Present the diff output for two mutliline strings in a pretty, colorised manner.
+///
+/// Other formats are self-describing, so nothing is written in them.
 pub(crate) fn write_header(f: &mut fmt::Formatter) -> fmt::Result {
+    if format() != Format::Pretty {
+        return Ok(());
+    }
     writeln!(
         f,
         "{} {} {} / {} {} :",
@@ -26,21 +78,65 @@ pub(crate) fn write_header(f: &mut fmt::Formatter) -> fmt::Result {
     )
 }
 
+/// Present the header for a line-by-line pattern match.
+#[cfg(feature = "regex")]
+pub(crate) fn write_match_header(f: &mut fmt::Formatter) -> fmt::Result {
+    writeln!(
+        f,
+        "{} {} {} / matched :",
+        "Lines".bold(),
+        SIGN_LEFT.red().linger(),
+        "unmatched".resetting(),
+    )
+}
+
+/// Present each line of `text`, highlighting those which do not satisfy `is_match`.
+///
+/// Matching lines are printed plain, in the same way as unchanged lines in a diff.
+#[cfg(feature = "regex")]
+pub(crate) fn write_matched_lines<TWrite, TMatch>(
+    f: &mut TWrite,
+    text: &str,
+    mut is_match: TMatch,
+) -> fmt::Result
+where
+    TWrite: fmt::Write,
+    TMatch: FnMut(&str) -> bool,
+{
+    for line in text.lines() {
+        if is_match(line) {
+            writeln!(f, " {}", line)?;
+        } else {
+            paint!(f, Red, "{}{}", SIGN_LEFT, line)?;
+            writeln!(f)?;
+        }
+    }
+    Ok(())
+}
+
 /// Delay formatting this deleted chunk until later.
 ///
 /// It can be formatted as a whole chunk by calling `flush`, or the inner value
 /// obtained with `take` for further processing (such as an inline diff).
-#[derive(Default)]
-struct LatentDeletion<'a> {
+struct LatentDeletion<T> {
     // The most recent deleted line we've seen
-    value: Option<&'a str>,
+    value: Option<T>,
     // The number of deleted lines we've seen, including the current value
     count: usize,
 }
 
-impl<'a> LatentDeletion<'a> {
+impl<T> Default for LatentDeletion<T> {
+    fn default() -> Self {
+        LatentDeletion {
+            value: None,
+            count: 0,
+        }
+    }
+}
+
+impl<T: Copy + fmt::Display> LatentDeletion<T> {
     /// Set the chunk value.
-    fn set(&mut self, value: &'a str) {
+    fn set(&mut self, value: T) {
         self.value = Some(value);
         self.count += 1;
     }
@@ -48,7 +144,7 @@ impl<'a> LatentDeletion<'a> {
     /// Take the underlying chunk value, if it's suitable for inline diffing.
     ///
     /// If there is no value or we've seen more than one line, return `None`.
-    fn take(&mut self) -> Option<&'a str> {
+    fn take(&mut self) -> Option<T> {
         if self.count == 1 {
             self.value.take()
         } else {
@@ -83,14 +179,709 @@ pub(crate) fn write_lines<TWrite: fmt::Write>(
     left: &str,
     right: &str,
 ) -> fmt::Result {
-    let diff = ::diff::lines(left, right);
+    #[cfg(feature = "std")]
+    crate::artifacts::capture(left, right);
+    match format() {
+        Format::Pretty => write_pretty_model(f, &whole_model(::diff::lines(left, right))),
+        #[cfg(feature = "std")]
+        Format::Unified => write_unified(f, left, right),
+        #[cfg(feature = "std")]
+        Format::Markdown => write_markdown_model(
+            f,
+            &diff_model(left, right, UNIFIED_CONTEXT),
+            Some(MARKDOWN_COLLAPSE_LINES),
+        ),
+        #[cfg(all(feature = "std", feature = "serde_json"))]
+        Format::Json => write_json_model(f, &diff_model(left, right, UNIFIED_CONTEXT)),
+    }
+}
+
+/// Present the diff output for two lists of lines, where lines are compared by a key.
+///
+/// Lines with equal keys are treated as unchanged, and the left line is printed.
+/// Otherwise, the original lines are printed in the same way as [`write_lines`].
+pub(crate) fn write_lines_by_key<TWrite, TKey, K>(
+    f: &mut TWrite,
+    left: &[&str],
+    right: &[&str],
+    key: TKey,
+) -> fmt::Result
+where
+    TWrite: fmt::Write,
+    TKey: Fn(&str) -> K,
+    K: PartialEq,
+{
+    #[cfg(feature = "std")]
+    crate::artifacts::capture(&left.join("\n"), &right.join("\n"));
+    match format() {
+        Format::Pretty => write_pretty_model(f, &whole_model(diff_by_key(left, right, key))),
+        #[cfg(feature = "std")]
+        Format::Unified => write_unified_by_key(f, left, right, key),
+        #[cfg(feature = "std")]
+        Format::Markdown => write_markdown_model(
+            f,
+            &diff_model_by_key(left, right, key),
+            Some(MARKDOWN_COLLAPSE_LINES),
+        ),
+        #[cfg(all(feature = "std", feature = "serde_json"))]
+        Format::Json => write_json_model(f, &diff_model_by_key(left, right, key)),
+    }
+}
+
+/// Present the diff output for two lists of lines as a plain unified diff, where lines are
+/// compared by a key.
+pub(crate) fn write_unified_by_key<TWrite, TKey, K>(
+    f: &mut TWrite,
+    left: &[&str],
+    right: &[&str],
+    key: TKey,
+) -> fmt::Result
+where
+    TWrite: fmt::Write,
+    TKey: Fn(&str) -> K,
+    K: PartialEq,
+{
+    write_unified_model(f, &diff_model_by_key(left, right, key))
+}
+
+/// Diff two lists of lines by a key, mapping each change back to the original lines.
+fn diff_by_key<'a, TKey, K>(
+    left: &[&'a str],
+    right: &[&'a str],
+    key: TKey,
+) -> Vec<::diff::Result<&'a str>>
+where
+    TKey: Fn(&str) -> K,
+    K: PartialEq,
+{
+    let left_keys: Vec<K> = left.iter().map(|line| key(line)).collect();
+    let right_keys: Vec<K> = right.iter().map(|line| key(line)).collect();
+
+    let (mut left_index, mut right_index) = (0, 0);
+    ::diff::slice(&left_keys, &right_keys)
+        .into_iter()
+        .map(|change| match change {
+            ::diff::Result::Both(..) => {
+                left_index += 1;
+                right_index += 1;
+                ::diff::Result::Both(left[left_index - 1], right[right_index - 1])
+            }
+            ::diff::Result::Left(_) => {
+                left_index += 1;
+                ::diff::Result::Left(left[left_index - 1])
+            }
+            ::diff::Result::Right(_) => {
+                right_index += 1;
+                ::diff::Result::Right(right[right_index - 1])
+            }
+        })
+        .collect()
+}
+
+/// Whether a diff shows the whole of both texts, as a single hunk from the first lines.
+///
+/// Otherwise, renderers show the range of lines in each hunk.
+fn is_whole(diff: &Diff) -> bool {
+    match diff.hunks.as_slice() {
+        [hunk] => hunk.left_start <= 1 && hunk.right_start <= 1,
+        _ => false,
+    }
+}
+
+/// Present a diff from the data model in a pretty, colorised manner.
+///
+/// Where a single line is replaced by another, the parts of each which differ are
+/// highlighted.
+pub(crate) fn write_pretty_model<TWrite: fmt::Write>(f: &mut TWrite, diff: &Diff) -> fmt::Result {
+    let whole = is_whole(diff);
+    for hunk in &diff.hunks {
+        if !whole {
+            let mut header = String::new();
+            write_hunk_header(&mut header, hunk)?;
+            paint!(f, Cyan, "{}", header)?;
+            writeln!(f)?;
+        }
+        for line in &hunk.lines {
+            match line.kind {
+                // If the text is unchanged, just print it plain
+                LineKind::Equal => writeln!(f, " {}", line.text)?,
+                LineKind::Delete if line.spans.is_empty() => {
+                    paint!(f, Red, "{}{}", SIGN_LEFT, line.text)?;
+                    writeln!(f)?;
+                }
+                LineKind::Insert if line.spans.is_empty() => {
+                    paint!(f, Green, "{}{}", SIGN_RIGHT, line.text)?;
+                    writeln!(f)?;
+                }
+                LineKind::Delete => {
+                    let heavy = Red.on_fixed(52).bold();
+                    write_inline_line(f, SIGN_LEFT, line, Red.into(), heavy)?;
+                }
+                LineKind::Insert => {
+                    let heavy = Green.on_fixed(22).bold();
+                    write_inline_line(f, SIGN_RIGHT, line, Green.into(), heavy)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The number of unchanged lines shown around each change in a unified diff.
+pub(crate) const UNIFIED_CONTEXT: usize = 3;
+
+/// Write the range of lines covered by a hunk, omitting the count if it is one.
+fn write_hunk_range<TWrite: fmt::Write>(f: &mut TWrite, start: usize, count: usize) -> fmt::Result {
+    match count {
+        1 => write!(f, "{}", start),
+        _ => write!(f, "{},{}", start, count),
+    }
+}
+
+/// Split text into lines, as a file is split by `diff`.
+///
+/// Returns the lines, and whether the last line ends with a newline.
+fn unified_lines(text: &str) -> (Vec<&str>, bool) {
+    match text.strip_suffix('\n') {
+        Some(text) => (text.split('\n').collect(), true),
+        None if text.is_empty() => (Vec::new(), true),
+        None => (text.split('\n').collect(), false),
+    }
+}
+
+/// Diff two multiline strings into the data model, as used for a unified diff.
+///
+/// Each hunk has the given number of unchanged lines of context around its changes.
+pub(crate) fn diff_model(left: &str, right: &str, context: usize) -> Diff {
+    let (left, left_newline) = unified_lines(left);
+    let (right, right_newline) = unified_lines(right);
+    let diff = ::diff::slice(&left, &right)
+        .into_iter()
+        .map(|change| match change {
+            ::diff::Result::Both(l, r) => ::diff::Result::Both(*l, *r),
+            ::diff::Result::Left(l) => ::diff::Result::Left(*l),
+            ::diff::Result::Right(r) => ::diff::Result::Right(*r),
+        })
+        .collect();
+    changes_model(diff, left_newline, right_newline, Some(context))
+}
+
+/// Diff two lists of lines into the data model, where lines are compared by a key.
+pub(crate) fn diff_model_by_key<TKey, K>(left: &[&str], right: &[&str], key: TKey) -> Diff
+where
+    TKey: Fn(&str) -> K,
+    K: PartialEq,
+{
+    changes_model(
+        diff_by_key(left, right, key),
+        true,
+        true,
+        Some(UNIFIED_CONTEXT),
+    )
+}
+
+/// Build the data model of a line diff as a single hunk, showing every line.
+fn whole_model(diff: Vec<::diff::Result<&str>>) -> Diff {
+    changes_model(diff, true, true, None)
+}
+
+/// The changed parts of two lines which replace one another, on each side.
+fn inline_spans(left: &str, right: &str) -> (Vec<Span>, Vec<Span>) {
+    fn push(spans: &mut Vec<Span>, start: usize, len: usize) {
+        match spans.last_mut() {
+            Some(span) if span.end == start => span.end += len,
+            _ => spans.push(Span {
+                start,
+                end: start + len,
+            }),
+        }
+    }
+
+    let (mut left_spans, mut right_spans) = (Vec::new(), Vec::new());
+    let (mut left_offset, mut right_offset) = (0, 0);
+    for change in ::diff::chars(left, right) {
+        match change {
+            ::diff::Result::Both(value, _) => {
+                left_offset += value.len_utf8();
+                right_offset += value.len_utf8();
+            }
+            ::diff::Result::Left(value) => {
+                push(&mut left_spans, left_offset, value.len_utf8());
+                left_offset += value.len_utf8();
+            }
+            ::diff::Result::Right(value) => {
+                push(&mut right_spans, right_offset, value.len_utf8());
+                right_offset += value.len_utf8();
+            }
+        }
+    }
+    (left_spans, right_spans)
+}
+
+/// Build the data model of a line diff, grouping changes into hunks.
+///
+/// The flags give whether the last line on each side ends with a newline. Each hunk has
+/// the given number of unchanged lines of context around its changes, or if there is no
+/// limit, every line is in a single hunk.
+fn changes_model(
+    mut diff: Vec<::diff::Result<&str>>,
+    left_newline: bool,
+    right_newline: bool,
+    context: Option<usize>,
+) -> Diff {
+    // The last lines differ if only one of them ends with a newline
+    if let (true, Some(&::diff::Result::Both(l, r))) = (left_newline != right_newline, diff.last())
+    {
+        diff.pop();
+        diff.push(::diff::Result::Left(l));
+        diff.push(::diff::Result::Right(r));
+    }
+
+    let left_len = diff
+        .iter()
+        .filter(|change| !matches!(change, ::diff::Result::Right(_)))
+        .count();
+    let right_len = diff
+        .iter()
+        .filter(|change| !matches!(change, ::diff::Result::Left(_)))
+        .count();
+
+    let mut lines = Vec::with_capacity(diff.len());
+    let (mut left_line, mut right_line) = (0, 0);
+    // The inline changes of an insertion which replaces the line before it
+    let mut replacement_spans = None;
+    for (index, change) in diff.iter().enumerate() {
+        let line = match *change {
+            ::diff::Result::Both(value, _) => {
+                left_line += 1;
+                right_line += 1;
+                Line {
+                    kind: LineKind::Equal,
+                    text: value.into(),
+                    left_line: Some(left_line),
+                    right_line: Some(right_line),
+                    spans: Vec::new(),
+                    missing_newline: left_line == left_len && !left_newline,
+                }
+            }
+            ::diff::Result::Left(value) => {
+                left_line += 1;
+                // A single line replaced by another is diffed inline
+                let previous = index.checked_sub(1).map(|index| &diff[index]);
+                let spans = match (previous, diff.get(index + 1), diff.get(index + 2)) {
+                    (previous, Some(::diff::Result::Right(inserted)), next)
+                        if !matches!(previous, Some(::diff::Result::Left(_)))
+                            && !matches!(next, Some(::diff::Result::Right(_))) =>
+                    {
+                        let (spans, inserted_spans) = inline_spans(value, inserted);
+                        replacement_spans = Some(inserted_spans);
+                        spans
+                    }
+                    _ => Vec::new(),
+                };
+                Line {
+                    kind: LineKind::Delete,
+                    text: value.into(),
+                    left_line: Some(left_line),
+                    right_line: None,
+                    spans,
+                    missing_newline: left_line == left_len && !left_newline,
+                }
+            }
+            ::diff::Result::Right(value) => {
+                right_line += 1;
+                Line {
+                    kind: LineKind::Insert,
+                    text: value.into(),
+                    left_line: None,
+                    right_line: Some(right_line),
+                    spans: replacement_spans.take().unwrap_or_default(),
+                    missing_newline: right_line == right_len && !right_newline,
+                }
+            }
+        };
+        lines.push(line);
+    }
+
+    // Group changes into hunks, merging those whose context would overlap
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    match context {
+        Some(context) => {
+            for (index, line) in lines.iter().enumerate() {
+                if line.kind == LineKind::Equal {
+                    continue;
+                }
+                let start = index.saturating_sub(context);
+                let end = (index + 1).saturating_add(context).min(lines.len());
+                match ranges.last_mut() {
+                    Some(range) if start <= range.1 => range.1 = end,
+                    _ => ranges.push((start, end)),
+                }
+            }
+        }
+        None if lines.is_empty() => (),
+        None => ranges.push((0, lines.len())),
+    }
+
+    let hunks = ranges
+        .into_iter()
+        .map(|(start, end)| {
+            let before = &lines[..start];
+            let hunk = &lines[start..end];
+            let left_before = before.iter().filter(|line| line.left_line.is_some());
+            let right_before = before.iter().filter(|line| line.right_line.is_some());
+            let left_count = hunk.iter().filter(|line| line.left_line.is_some()).count();
+            let right_count = hunk.iter().filter(|line| line.right_line.is_some()).count();
+            Hunk {
+                left_start: left_before.count() + usize::from(left_count > 0),
+                left_count,
+                right_start: right_before.count() + usize::from(right_count > 0),
+                right_count,
+                lines: hunk.to_vec(),
+            }
+        })
+        .collect();
+
+    Diff {
+        left_label: "left".into(),
+        right_label: "right".into(),
+        hunks,
+    }
+}
+
+/// Present the diff output for two multiline strings as a plain unified diff.
+///
+/// The output is in the format of `diff -u`, with the files named `left` and `right`,
+/// so it can be read by tools such as `patch` and `git apply`. Nothing is written if the
+/// strings are equal.
+pub(crate) fn write_unified<TWrite: fmt::Write>(
+    f: &mut TWrite,
+    left: &str,
+    right: &str,
+) -> fmt::Result {
+    write_unified_model(f, &diff_model(left, right, UNIFIED_CONTEXT))
+}
+
+/// Write the header of a hunk, giving the range of lines it covers on each side.
+fn write_hunk_header<TWrite: fmt::Write>(f: &mut TWrite, hunk: &Hunk) -> fmt::Result {
+    write!(f, "@@ -")?;
+    write_hunk_range(f, hunk.left_start, hunk.left_count)?;
+    write!(f, " +")?;
+    write_hunk_range(f, hunk.right_start, hunk.right_count)?;
+    write!(f, " @@")
+}
+
+/// Present a diff from the data model as a plain unified diff.
+pub(crate) fn write_unified_model<TWrite: fmt::Write>(f: &mut TWrite, diff: &Diff) -> fmt::Result {
+    if diff.hunks.is_empty() {
+        return Ok(());
+    }
+    writeln!(f, "--- {}", diff.left_label)?;
+    writeln!(f, "+++ {}", diff.right_label)?;
+    for hunk in &diff.hunks {
+        write_hunk_header(f, hunk)?;
+        writeln!(f)?;
+        for line in &hunk.lines {
+            let sign = match line.kind {
+                LineKind::Equal => ' ',
+                LineKind::Delete => '-',
+                LineKind::Insert => '+',
+            };
+            writeln!(f, "{}{}", sign, line.text)?;
+            if line.missing_newline {
+                writeln!(f, "\\ No newline at end of file")?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The number of lines above which diffs in assertion failures are collapsed, in the
+/// Markdown format.
+#[cfg(feature = "std")]
+const MARKDOWN_COLLAPSE_LINES: usize = 50;
+
+/// Present a diff from the data model as a unified diff in a fenced Markdown code block.
+///
+/// The block is marked as `diff`, so that it is colorised by renderers such as GitHub's.
+/// If the diff has more lines than the limit, it is wrapped in a collapsed `<details>`
+/// section. Nothing is written if there are no hunks.
+pub(crate) fn write_markdown_model<TWrite: fmt::Write>(
+    f: &mut TWrite,
+    diff: &Diff,
+    collapse_after: Option<usize>,
+) -> fmt::Result {
+    if diff.hunks.is_empty() {
+        return Ok(());
+    }
+    let mut unified = String::new();
+    write_unified_model(&mut unified, diff)?;
+
+    let lines = diff.hunks.iter().flat_map(|hunk| &hunk.lines);
+    let collapsed = matches!(collapse_after, Some(limit) if lines.clone().count() > limit);
+    if collapsed {
+        let count = |kind| lines.clone().filter(|line| line.kind == kind).count();
+        writeln!(f, "<details>")?;
+        writeln!(
+            f,
+            "<summary>Diff: {} removed, {} added</summary>",
+            count(LineKind::Delete),
+            count(LineKind::Insert)
+        )?;
+        writeln!(f)?;
+    }
+    // The fence must be longer than any run of backticks in the diff
+    let longest = unified.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat((longest + 1).max(3));
+    writeln!(f, "{}diff", fence)?;
+    f.write_str(&unified)?;
+    writeln!(f, "{}", fence)?;
+    if collapsed {
+        writeln!(f)?;
+        writeln!(f, "</details>")?;
+    }
+    Ok(())
+}
+
+/// Present a diff from the data model as pretty-printed JSON.
+#[cfg(all(feature = "std", feature = "serde_json"))]
+fn write_json_model<TWrite: fmt::Write>(f: &mut TWrite, diff: &Diff) -> fmt::Result {
+    let json = ::serde_json::to_string_pretty(diff).map_err(|_| fmt::Error)?;
+    writeln!(f, "{}", json)
+}
+
+/// The stylesheet included with each HTML diff, scoped to its container.
+const HTML_STYLE: &str = "<style>
+.pretty-assertions-diff table { border-collapse: collapse; font-family: monospace; }
+.pretty-assertions-diff td { padding: 0 0.5em; white-space: pre; vertical-align: top; }
+.pretty-assertions-diff td.line { color: #6e7781; text-align: right; user-select: none; }
+.pretty-assertions-diff tr.left { background: #ffebe9; }
+.pretty-assertions-diff tr.right { background: #e6ffec; }
+.pretty-assertions-diff tr.hunk { background: #ddf4ff; color: #6e7781; }
+.pretty-assertions-diff del { background: #ff8182; text-decoration: none; }
+.pretty-assertions-diff ins { background: #abf2bc; text-decoration: none; }
+</style>";
+
+/// Write text with the characters which are special in HTML escaped.
+fn write_html_escaped<TWrite: fmt::Write>(f: &mut TWrite, text: &str) -> fmt::Result {
+    let mut rest = text;
+    while let Some(index) = rest.find(['&', '<', '>', '"', '\'']) {
+        f.write_str(&rest[..index])?;
+        let entity = match rest.as_bytes()[index] {
+            b'&' => "&amp;",
+            b'<' => "&lt;",
+            b'>' => "&gt;",
+            b'"' => "&quot;",
+            _ => "&#39;",
+        };
+        f.write_str(entity)?;
+        rest = &rest[index + 1..];
+    }
+    f.write_str(rest)
+}
+
+/// Write a single table row of an HTML diff.
+///
+/// The class is `both`, `left` or `right`, and a line number is given for each side the
+/// line appears on. The contents are written by the given closure.
+fn write_html_row<TWrite, TContents>(
+    f: &mut TWrite,
+    class: &str,
+    left_line: Option<usize>,
+    right_line: Option<usize>,
+    contents: TContents,
+) -> fmt::Result
+where
+    TWrite: fmt::Write,
+    TContents: FnOnce(&mut TWrite) -> fmt::Result,
+{
+    write!(f, "<tr class=\"{}\">", class)?;
+    for line in [left_line, right_line] {
+        match line {
+            Some(line) => write!(f, "<td class=\"line\">{}</td>", line)?,
+            None => write!(f, "<td class=\"line\"></td>")?,
+        }
+    }
+    let sign = match class {
+        "left" => '-',
+        "right" => '+',
+        _ => ' ',
+    };
+    write!(f, "<td class=\"sign\">{}</td><td>", sign)?;
+    contents(f)?;
+    writeln!(f, "</td></tr>")
+}
+
+/// Write the text of a line, wrapping each changed part in the tag.
+fn write_html_text<TWrite: fmt::Write>(f: &mut TWrite, line: &Line, tag: &str) -> fmt::Result {
+    let mut offset = 0;
+    for span in &line.spans {
+        write_html_escaped(f, &line.text[offset..span.start])?;
+        write!(f, "<{}>", tag)?;
+        write_html_escaped(f, &line.text[span.start..span.end])?;
+        write!(f, "</{}>", tag)?;
+        offset = span.end;
+    }
+    write_html_escaped(f, &line.text[offset..])
+}
+
+/// Present the diff output for two multiline strings as self-contained HTML.
+///
+/// Each line is a row of a table, with the line numbers on each side. Where a single
+/// line is replaced, the characters which differ are marked with `<del>` and `<ins>`.
+pub(crate) fn write_html<TWrite: fmt::Write>(
+    f: &mut TWrite,
+    left: &str,
+    right: &str,
+) -> fmt::Result {
+    write_html_model(f, &whole_model(::diff::lines(left, right)))
+}
+
+/// Present the diff output for two lists of lines as self-contained HTML, where lines are
+/// compared by a key.
+pub(crate) fn write_html_by_key<TWrite, TKey, K>(
+    f: &mut TWrite,
+    left: &[&str],
+    right: &[&str],
+    key: TKey,
+) -> fmt::Result
+where
+    TWrite: fmt::Write,
+    TKey: Fn(&str) -> K,
+    K: PartialEq,
+{
+    write_html_model(f, &whole_model(diff_by_key(left, right, key)))
+}
+
+/// Present a diff from the data model as self-contained HTML.
+///
+/// Unless the diff shows every line, each hunk starts with a row giving its range of lines.
+pub(crate) fn write_html_model<TWrite: fmt::Write>(f: &mut TWrite, diff: &Diff) -> fmt::Result {
+    writeln!(f, "<div class=\"pretty-assertions-diff\">")?;
+    writeln!(f, "{}", HTML_STYLE)?;
+    writeln!(f, "<table>")?;
+    let whole = is_whole(diff);
+    for hunk in &diff.hunks {
+        if !whole {
+            write!(f, "<tr class=\"hunk\"><td colspan=\"4\">")?;
+            write_hunk_header(f, hunk)?;
+            writeln!(f, "</td></tr>")?;
+        }
+        for line in &hunk.lines {
+            let (class, tag) = match line.kind {
+                LineKind::Equal => ("both", ""),
+                LineKind::Delete => ("left", "del"),
+                LineKind::Insert => ("right", "ins"),
+            };
+            write_html_row(f, class, line.left_line, line.right_line, |f| {
+                write_html_text(f, line, tag)
+            })?;
+        }
+    }
+    writeln!(f, "</table>")?;
+    writeln!(f, "</div>")
+}
+
+/// The number of bytes shown in each row of a hex dump.
+const BYTES_PER_ROW: usize = 16;
+
+/// A single row of a hex dump, in the style of `xxd`.
+///
+/// Displays as the offset of the row, followed by the bytes in hex and ASCII.
+#[derive(Clone, Copy)]
+struct BytesRow<'a> {
+    offset: usize,
+    bytes: &'a [u8],
+}
+
+impl<'a> BytesRow<'a> {
+    /// Split a byte slice into rows.
+    fn split(bytes: &'a [u8]) -> impl Iterator<Item = BytesRow<'a>> {
+        bytes
+            .chunks(BYTES_PER_ROW)
+            .enumerate()
+            .map(|(index, bytes)| BytesRow {
+                offset: index * BYTES_PER_ROW,
+                bytes,
+            })
+    }
+
+    /// Write the row, calling `style_for` to get the style of the byte at each index.
+    ///
+    /// A `None` byte index is used for the offset and padding.
+    fn write_styled<TWrite, TStyle>(
+        &self,
+        writer: &mut InlineWriter<TWrite>,
+        style_for: TStyle,
+    ) -> fmt::Result
+    where
+        TWrite: fmt::Write,
+        TStyle: Fn(Option<usize>) -> Style,
+    {
+        let mut write_str = |value: &str, style: Style| -> fmt::Result {
+            for c in value.chars() {
+                writer.write_with_style(&c, style)?;
+            }
+            Ok(())
+        };
+
+        write_str(&format!("{:08x}: ", self.offset), style_for(None))?;
+        for index in 0..BYTES_PER_ROW {
+            match self.bytes.get(index) {
+                Some(byte) => write_str(&format!("{:02x}", byte), style_for(Some(index)))?,
+                None => write_str("  ", style_for(None))?,
+            }
+            if index % 2 == 1 {
+                write_str(" ", style_for(None))?;
+            }
+        }
+        write_str(" ", style_for(None))?;
+        for (index, byte) in self.bytes.iter().enumerate() {
+            let c = if byte.is_ascii_graphic() || *byte == b' ' {
+                *byte as char
+            } else {
+                '.'
+            };
+            writer.write_with_style(&c, style_for(Some(index)))?;
+        }
+        Ok(())
+    }
+}
+
+/// Rows are compared by their bytes only, so that unchanged rows are matched even if
+/// their offset has changed.
+impl PartialEq for BytesRow<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes == other.bytes
+    }
+}
+
+impl fmt::Display for BytesRow<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut writer = InlineWriter::new(f);
+        self.write_styled(&mut writer, |_| Style::new())
+    }
+}
+
+/// Present the diff output for two byte slices as a hex dump, in a pretty, colorised manner.
+///
+/// Rows are diffed as a whole. Where a single row has changed, the bytes which differ
+/// are highlighted inline.
+pub(crate) fn write_bytes<TWrite: fmt::Write>(
+    f: &mut TWrite,
+    left: &[u8],
+    right: &[u8],
+) -> fmt::Result {
+    let left_rows: Vec<BytesRow> = BytesRow::split(left).collect();
+    let right_rows: Vec<BytesRow> = BytesRow::split(right).collect();
+    let diff = ::diff::slice(&left_rows, &right_rows);
 
     let mut changes = diff.into_iter().peekable();
     let mut previous_deletion = LatentDeletion::default();
 
     while let Some(change) = changes.next() {
         match (change, changes.peek()) {
-            // If the text is unchanged, just print it plain
+            // If the row is unchanged, just print it plain
             (::diff::Result::Both(value, _), _) => {
                 previous_deletion.flush(f)?;
                 writeln!(f, " {}", value)?;
@@ -98,7 +889,7 @@ pub(crate) fn write_lines<TWrite: fmt::Write>(
             // Defer any deletions to next loop
             (::diff::Result::Left(deleted), _) => {
                 previous_deletion.flush(f)?;
-                previous_deletion.set(deleted);
+                previous_deletion.set(*deleted);
             }
             // If we're being followed by more insertions, don't inline diff
             (::diff::Result::Right(inserted), Some(::diff::Result::Right(_))) => {
@@ -106,10 +897,10 @@ pub(crate) fn write_lines<TWrite: fmt::Write>(
                 paint!(f, Green, "{}{}", SIGN_RIGHT, inserted)?;
                 writeln!(f)?;
             }
-            // Otherwise, check if we need to inline diff with the previous line (if it was a deletion)
+            // Otherwise, check if we need to inline diff with the previous row (if it was a deletion)
             (::diff::Result::Right(inserted), _) => {
                 if let Some(deleted) = previous_deletion.take() {
-                    write_inline_diff(f, deleted, inserted)?;
+                    write_inline_bytes_diff(f, deleted, *inserted)?;
                 } else {
                     previous_deletion.flush(f)?;
                     paint!(f, Green, "{}{}", SIGN_RIGHT, inserted)?;
@@ -123,6 +914,40 @@ pub(crate) fn write_lines<TWrite: fmt::Write>(
     Ok(())
 }
 
+/// Format a single row to show an inline diff of the two rows given.
+///
+/// Bytes are compared by their position in the row, rather than by a character diff.
+///
+/// The output of this function will be two lines, each with a trailing newline.
+fn write_inline_bytes_diff<TWrite: fmt::Write>(
+    f: &mut TWrite,
+    left: BytesRow,
+    right: BytesRow,
+) -> fmt::Result {
+    let mut writer = InlineWriter::new(f);
+    let changed = |index: usize| left.bytes.get(index) != right.bytes.get(index);
+
+    // Print the left row on one line, with differences highlighted
+    let light = Style::from(Red);
+    let heavy = Red.on_fixed(52).bold();
+    writer.write_with_style(&SIGN_LEFT, light)?;
+    left.write_styled(&mut writer, |index| match index {
+        Some(index) if changed(index) => heavy,
+        _ => light,
+    })?;
+    writer.finish()?;
+
+    // Print the right row on one line, with differences highlighted
+    let light = Style::from(Green);
+    let heavy = Green.on_fixed(22).bold();
+    writer.write_with_style(&SIGN_RIGHT, light)?;
+    right.write_styled(&mut writer, |index| match index {
+        Some(index) if changed(index) => heavy,
+        _ => light,
+    })?;
+    writer.finish()
+}
+
 /// Group character styling for an inline diff, to prevent wrapping each single
 /// character in terminal styling codes.
 ///
@@ -171,38 +996,31 @@ where
     }
 }
 
-/// Format a single line to show an inline diff of the two strings given.
-///
-/// The given strings should not have a trailing newline.
+/// Format a single line of a replacement, with the parts which differ highlighted.
 ///
-/// The output of this function will be two lines, each with a trailing newline.
-fn write_inline_diff<TWrite: fmt::Write>(f: &mut TWrite, left: &str, right: &str) -> fmt::Result {
-    let diff = ::diff::chars(left, right);
+/// The output is the sign and text of the line, in the light style, with each of its
+/// spans in the heavy style, followed by a newline.
+fn write_inline_line<TWrite: fmt::Write>(
+    f: &mut TWrite,
+    sign: char,
+    line: &Line,
+    light: Style,
+    heavy: Style,
+) -> fmt::Result {
     let mut writer = InlineWriter::new(f);
-
-    // Print the left string on one line, with differences highlighted
-    let light = Red;
-    let heavy = Red.on_fixed(52).bold();
-    writer.write_with_style(&SIGN_LEFT, light)?;
-    for change in diff.iter() {
-        match change {
-            ::diff::Result::Both(value, _) => writer.write_with_style(value, light)?,
-            ::diff::Result::Left(value) => writer.write_with_style(value, heavy)?,
-            _ => (),
+    writer.write_with_style(&sign, light)?;
+    let mut offset = 0;
+    for span in &line.spans {
+        for c in line.text[offset..span.start].chars() {
+            writer.write_with_style(&c, light)?;
         }
-    }
-    writer.finish()?;
-
-    // Print the right string on one line, with differences highlighted
-    let light = Green;
-    let heavy = Green.on_fixed(22).bold();
-    writer.write_with_style(&SIGN_RIGHT, light)?;
-    for change in diff.iter() {
-        match change {
-            ::diff::Result::Both(value, _) => writer.write_with_style(value, light)?,
-            ::diff::Result::Right(value) => writer.write_with_style(value, heavy)?,
-            _ => (),
+        for c in line.text[span.start..span.end].chars() {
+            writer.write_with_style(&c, heavy)?;
         }
+        offset = span.end;
+    }
+    for c in line.text[offset..].chars() {
+        writer.write_with_style(&c, light)?;
     }
     writer.finish()
 }
@@ -223,6 +1041,26 @@ mod test {
     const GREEN_HEAVY: &str = "\u{1b}[1;48;5;22;32m";
     const RESET: &str = "\u{1b}[0m";
 
+    /// Format two lines to show an inline diff of the strings given, as the colorised
+    /// output shows a single line replaced by another.
+    fn write_inline_diff(f: &mut String, left: &str, right: &str) -> fmt::Result {
+        let (left_spans, right_spans) = inline_spans(left, right);
+        let line = |kind, text: &str, spans| Line {
+            kind,
+            text: text.into(),
+            left_line: None,
+            right_line: None,
+            spans,
+            missing_newline: false,
+        };
+        let deleted = line(LineKind::Delete, left, left_spans);
+        let inserted = line(LineKind::Insert, right, right_spans);
+        let heavy = Red.on_fixed(52).bold();
+        write_inline_line(f, SIGN_LEFT, &deleted, Red.into(), heavy)?;
+        let heavy = Green.on_fixed(22).bold();
+        write_inline_line(f, SIGN_RIGHT, &inserted, Green.into(), heavy)
+    }
+
     /// Given that both of our diff printing functions have the same
     /// type signature, we can reuse the same test code for them.
     ///
@@ -478,6 +1316,84 @@ Cabbage"#;
         check_printer(write_lines, left, right, &expected);
     }
 
+    #[cfg(feature = "regex")]
+    #[test]
+    fn write_matched_lines_mixed() {
+        let text = "foo=1\nbar=x\nbaz=3";
+        let expected = format!(
+            " foo=1\n\
+             {red_light}<bar=x{reset}\n \
+             baz=3\n",
+            red_light = RED_LIGHT,
+            reset = RESET,
+        );
+
+        check_printer(
+            |f, text, _| write_matched_lines(f, text, |line| !line.ends_with('x')),
+            text,
+            "",
+            &expected,
+        );
+    }
+
+    /// Unchanged rows are printed plain, with padding for a partial final row.
+    #[test]
+    fn write_bytes_unchanged() {
+        let bytes = b"Hello World!\nline two\x00";
+        let expected = " 00000000: 4865 6c6c 6f20 576f 726c 6421 0a6c 696e  Hello World!.lin\n \
+            00000010: 6520 7477 6f00                           e two.\n";
+
+        check_printer(
+            |f, left, right| write_bytes(f, left.as_bytes(), right.as_bytes()),
+            core::str::from_utf8(bytes).unwrap(),
+            core::str::from_utf8(bytes).unwrap(),
+            expected,
+        );
+    }
+
+    /// A single changed row is diffed inline, by byte position.
+    #[test]
+    fn write_bytes_changed() {
+        let left = "Hello World!";
+        let right = "Hello Wrold!";
+        let expected = format!(
+            "{red_light}<00000000: 4865 6c6c 6f20 57{reset}{red_heavy}6f{reset}{red_light} {reset}{red_heavy}72{reset}{red_light}6c 6421            Hello W{reset}{red_heavy}or{reset}{red_light}ld!{reset}\n\
+             {green_light}>00000000: 4865 6c6c 6f20 57{reset}{green_heavy}72{reset}{green_light} {reset}{green_heavy}6f{reset}{green_light}6c 6421            Hello W{reset}{green_heavy}ro{reset}{green_light}ld!{reset}\n",
+            red_light = RED_LIGHT,
+            red_heavy = RED_HEAVY,
+            green_light = GREEN_LIGHT,
+            green_heavy = GREEN_HEAVY,
+            reset = RESET,
+        );
+
+        check_printer(
+            |f, left, right| write_bytes(f, left.as_bytes(), right.as_bytes()),
+            left,
+            right,
+            &expected,
+        );
+    }
+
+    /// Bytes appended in a new row are shown as a whole inserted row.
+    #[test]
+    fn write_bytes_appended() {
+        let left = "0123456789abcdef";
+        let right = "0123456789abcdefgh";
+        let expected = format!(
+            " 00000000: 3031 3233 3435 3637 3839 6162 6364 6566  0123456789abcdef\n\
+             {green_light}>00000010: 6768                                     gh{reset}\n",
+            green_light = GREEN_LIGHT,
+            reset = RESET,
+        );
+
+        check_printer(
+            |f, left, right| write_bytes(f, left.as_bytes(), right.as_bytes()),
+            left,
+            right,
+            &expected,
+        );
+    }
+
     mod write_lines_edge_newlines {
         use super::*;
 
@@ -598,4 +1514,275 @@ Cabbage"#;
             check_printer(write_lines, left, right, &expected);
         }
     }
+
+    mod write_unified {
+        use super::*;
+
+        fn unified(left: &str, right: &str) -> String {
+            let mut actual = String::new();
+            write_unified(&mut actual, left, right).expect("printer function failed");
+            actual
+        }
+
+        #[test]
+        fn separate_hunks() {
+            let left = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n";
+            let right = "one\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\n";
+            let expected = "--- left
++++ right
+@@ -1,4 +1,4 @@
+-1
++one
+ 2
+ 3
+ 4
+@@ -8,3 +8,4 @@
+ 8
+ 9
+ 10
++11
+";
+            assert_eq!(unified(left, right), expected);
+        }
+
+        #[test]
+        fn empty_range() {
+            let expected = "--- left
++++ right
+@@ -0,0 +1,2 @@
++a
++b
+";
+            assert_eq!(unified("", "a\nb\n"), expected);
+        }
+
+        #[test]
+        fn missing_newline() {
+            let expected = "--- left
++++ right
+@@ -1,2 +1,2 @@
+ a
+-b
+\\ No newline at end of file
++b
+";
+            assert_eq!(unified("a\nb", "a\nb\n"), expected);
+        }
+
+        #[test]
+        fn equal() {
+            assert_eq!(unified("a\n", "a\n"), "");
+        }
+    }
+    mod write_html {
+        use super::*;
+
+        fn html(left: &str, right: &str) -> String {
+            let mut actual = String::new();
+            write_html(&mut actual, left, right).expect("printer function failed");
+            actual
+        }
+
+        /// The table rows of an HTML diff, without the surrounding container and stylesheet.
+        fn rows(html: &str) -> Vec<&str> {
+            html.lines()
+                .filter(|line| line.starts_with("<tr"))
+                .collect()
+        }
+
+        #[test]
+        fn inline_changes() {
+            let actual = html("a\nfoo <bar>\n", "a\nfoo <baz>\n");
+            assert!(actual.starts_with("<div class=\"pretty-assertions-diff\">\n<style>\n"));
+            assert!(actual.ends_with("</table>\n</div>\n"));
+            assert_eq!(
+                rows(&actual),
+                [
+                    "<tr class=\"both\"><td class=\"line\">1</td><td class=\"line\">1</td><td class=\"sign\"> </td><td>a</td></tr>",
+                    "<tr class=\"left\"><td class=\"line\">2</td><td class=\"line\"></td><td class=\"sign\">-</td><td>foo &lt;ba<del>r</del>&gt;</td></tr>",
+                    "<tr class=\"right\"><td class=\"line\"></td><td class=\"line\">2</td><td class=\"sign\">+</td><td>foo &lt;ba<ins>z</ins>&gt;</td></tr>",
+                    "<tr class=\"both\"><td class=\"line\">3</td><td class=\"line\">3</td><td class=\"sign\"> </td><td></td></tr>",
+                ]
+            );
+        }
+
+        #[test]
+        fn multiple_insertions() {
+            let actual = html("a\n", "b\nc\n");
+            assert_eq!(
+                rows(&actual),
+                [
+                    "<tr class=\"left\"><td class=\"line\">1</td><td class=\"line\"></td><td class=\"sign\">-</td><td>a</td></tr>",
+                    "<tr class=\"right\"><td class=\"line\"></td><td class=\"line\">1</td><td class=\"sign\">+</td><td>b</td></tr>",
+                    "<tr class=\"right\"><td class=\"line\"></td><td class=\"line\">2</td><td class=\"sign\">+</td><td>c</td></tr>",
+                    "<tr class=\"both\"><td class=\"line\">2</td><td class=\"line\">3</td><td class=\"sign\"> </td><td></td></tr>",
+                ]
+            );
+        }
+
+        #[test]
+        fn escaped() {
+            let mut actual = String::new();
+            write_html_escaped(&mut actual, "<a href=\"x\">Tom & Jerry's</a>").unwrap();
+            assert_eq!(
+                actual,
+                "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;"
+            );
+        }
+    }
+    mod diff_model {
+        use super::*;
+
+        #[test]
+        fn numbers_and_spans() {
+            let diff = diff_model("a\nb\nc\n", "a\nB c\nc\nd", UNIFIED_CONTEXT);
+            assert_eq!(diff.hunks.len(), 1);
+            let hunk = &diff.hunks[0];
+            assert_eq!(
+                (
+                    hunk.left_start,
+                    hunk.left_count,
+                    hunk.right_start,
+                    hunk.right_count
+                ),
+                (1, 3, 1, 4)
+            );
+            let line = |kind, text: &str, left_line, right_line, spans: &[Span]| Line {
+                kind,
+                text: text.into(),
+                left_line,
+                right_line,
+                spans: spans.to_vec(),
+                missing_newline: false,
+            };
+            let span = |start, end| Span { start, end };
+            let mut inserted = line(LineKind::Insert, "d", None, Some(4), &[]);
+            inserted.missing_newline = true;
+            assert_eq!(
+                hunk.lines,
+                [
+                    line(LineKind::Equal, "a", Some(1), Some(1), &[]),
+                    line(LineKind::Delete, "b", Some(2), None, &[span(0, 1)]),
+                    line(LineKind::Insert, "B c", None, Some(2), &[span(0, 3)]),
+                    line(LineKind::Equal, "c", Some(3), Some(3), &[]),
+                    inserted,
+                ]
+            );
+        }
+
+        #[test]
+        fn equal() {
+            assert_eq!(diff_model("a\nb", "a\nb", UNIFIED_CONTEXT).hunks, []);
+        }
+
+        #[test]
+        fn pretty_hunk_headers() {
+            let diff = diff_model("a\nb\nc\nd\ne\n", "a\nb\nc\nd\nE\n", 1);
+            let mut actual = String::new();
+            write_pretty_model(&mut actual, &diff).unwrap();
+            assert_eq!(
+                actual,
+                "\u{1b}[36m@@ -4,2 +4,2 @@\u{1b}[0m\n d\n\u{1b}[31m<\u{1b}[0m\u{1b}[1;48;5;52;31me\u{1b}[0m\n\u{1b}[32m>\u{1b}[0m\u{1b}[1;48;5;22;32mE\u{1b}[0m\n"
+            );
+
+            let whole = whole_model(::diff::lines("a\nb", "a\nc"));
+            let mut actual = String::new();
+            write_pretty_model(&mut actual, &whole).unwrap();
+            assert!(actual.starts_with(" a\n"));
+        }
+
+        #[cfg(all(feature = "std", feature = "serde_json"))]
+        #[test]
+        fn json() {
+            let mut actual = String::new();
+            write_json_model(&mut actual, &diff_model("a\n", "b\n", UNIFIED_CONTEXT)).unwrap();
+            let json: ::serde_json::Value = ::serde_json::from_str(&actual).unwrap();
+            assert_eq!(
+                json,
+                ::serde_json::json!({
+                    "left_label": "left",
+                    "right_label": "right",
+                    "hunks": [{
+                        "left_start": 1,
+                        "left_count": 1,
+                        "right_start": 1,
+                        "right_count": 1,
+                        "lines": [
+                            {
+                                "kind": "delete",
+                                "text": "a",
+                                "left_line": 1,
+                                "right_line": null,
+                                "spans": [{"start": 0, "end": 1}],
+                                "missing_newline": false
+                            },
+                            {
+                                "kind": "insert",
+                                "text": "b",
+                                "left_line": null,
+                                "right_line": 1,
+                                "spans": [{"start": 0, "end": 1}],
+                                "missing_newline": false
+                            }
+                        ]
+                    }]
+                })
+            );
+        }
+    }
+    mod write_markdown {
+        use super::*;
+
+        fn markdown(left: &str, right: &str, collapse_after: Option<usize>) -> String {
+            let mut actual = String::new();
+            let diff = diff_model(left, right, UNIFIED_CONTEXT);
+            write_markdown_model(&mut actual, &diff, collapse_after).unwrap();
+            actual
+        }
+
+        #[test]
+        fn fenced() {
+            let expected = "```diff
+--- left
++++ right
+@@ -1,2 +1,2 @@
+ a
+-b
++c
+```
+";
+            assert_eq!(markdown("a\nb\n", "a\nc\n", Some(4)), expected);
+        }
+
+        #[test]
+        fn collapsed() {
+            let expected = "<details>
+<summary>Diff: 1 removed, 2 added</summary>
+
+```diff
+--- left
++++ right
+@@ -1 +1,2 @@
+-a
++b
++c
+```
+
+</details>
+";
+            assert_eq!(markdown("a\n", "b\nc\n", Some(2)), expected);
+        }
+
+        #[test]
+        fn longer_fence() {
+            let actual = markdown("```\n", "````\n", None);
+            assert!(actual.starts_with("`````diff\n"));
+            assert!(actual.ends_with("\n`````\n"));
+        }
+
+        #[test]
+        fn equal() {
+            assert_eq!(markdown("a\n", "a\n", Some(0)), "");
+        }
+    }
 }
@@ -0,0 +1,372 @@
+#[cfg(feature = "alloc")]
+use alloc::{borrow::ToOwned, format, string::String, vec::Vec};
+
+/// The text which replaces a redacted value.
+const REDACTED: &str = "[redacted]";
+
+/// A rule for hiding part of a value's [`Debug`](core::fmt::Debug) representation.
+///
+/// Use redactions for values which are expected to differ between runs, such as
+/// timestamps, UUIDs and memory addresses. Each redacted value is replaced with
+/// `[redacted]`, both before values are checked for equality and before they are diffed.
+///
+/// There are three kinds of rule:
+///
+/// - [`Redaction::field`] redacts every field with the given name, at any depth.
+/// - [`Redaction::path`] redacts the value at the given path from the root, such as
+///   `.user.created_at`, `.items[0].id` or `.pair.1`. The index `[*]` matches any
+///   element of a sequence or map.
+/// - [`Redaction::pattern`] redacts every match of a regular expression, on each line.
+///
+/// A string converts to a path if it starts with `.`, and to a field name otherwise.
+///
+/// ```
+/// use pretty_assertions::{Comparison, Redaction};
+///
+/// #[derive(Debug)]
+/// struct User {
+///     id: u64,
+///     name: &'static str,
+/// }
+///
+/// let left = User { id: 17, name: "Alice" };
+/// let right = User { id: 23, name: "Alice" };
+/// let comparison = Comparison::new(&left, &right).redact(Redaction::field("id"));
+/// assert!(comparison.is_equal());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Redaction {
+    rule: Rule,
+}
+
+#[derive(Clone, Debug)]
+enum Rule {
+    Field(String),
+    Path(String),
+    #[cfg(feature = "regex")]
+    Pattern(::regex::Regex),
+}
+
+impl Redaction {
+    /// Redact every field with the given name, at any depth.
+    pub fn field<T: Into<String>>(name: T) -> Redaction {
+        Redaction {
+            rule: Rule::Field(name.into()),
+        }
+    }
+
+    /// Redact the value at the given path from the root, such as `.user.created_at`.
+    ///
+    /// Struct fields are written as `.name`, and tuple fields as `.0`. Elements of a
+    /// sequence are written as `[0]`, and entries of a map by their debug key, as in
+    /// `["key"]`. The index `[*]` matches any element or entry.
+    pub fn path<T: Into<String>>(path: T) -> Redaction {
+        Redaction {
+            rule: Rule::Path(path.into()),
+        }
+    }
+
+    /// Redact every match of a regular expression, on each line.
+    ///
+    /// # Features
+    ///
+    /// Requires the `regex` feature to be enabled.
+    #[cfg(feature = "regex")]
+    pub fn pattern(regex: ::regex::Regex) -> Redaction {
+        Redaction {
+            rule: Rule::Pattern(regex),
+        }
+    }
+
+    /// Whether this rule redacts the value at the given path.
+    fn matches(&self, path: &str, field: Option<&str>) -> bool {
+        match &self.rule {
+            Rule::Field(name) => field == Some(name.as_str()),
+            Rule::Path(pattern) => path_matches(pattern, path),
+            #[cfg(feature = "regex")]
+            Rule::Pattern(_) => false,
+        }
+    }
+}
+
+impl From<&str> for Redaction {
+    fn from(rule: &str) -> Redaction {
+        if rule.starts_with('.') || rule.starts_with('[') {
+            Redaction::path(rule)
+        } else {
+            Redaction::field(rule)
+        }
+    }
+}
+
+#[cfg(feature = "regex")]
+impl From<::regex::Regex> for Redaction {
+    fn from(regex: ::regex::Regex) -> Redaction {
+        Redaction::pattern(regex)
+    }
+}
+
+/// Whether a path matches a pattern, where `[*]` in the pattern matches any index.
+fn path_matches(pattern: &str, path: &str) -> bool {
+    if let Some(pattern) = pattern.strip_prefix("[*]") {
+        return path.starts_with('[')
+            && match path.find(']') {
+                Some(end) => path_matches(pattern, &path[end + 1..]),
+                None => false,
+            };
+    }
+    match (pattern.chars().next(), path.chars().next()) {
+        (None, None) => true,
+        (Some(p), Some(c)) if p == c => {
+            path_matches(&pattern[p.len_utf8()..], &path[c.len_utf8()..])
+        }
+        _ => false,
+    }
+}
+
+/// The kind of block opened by a line of `{:#?}` output.
+#[derive(Clone, Copy, PartialEq)]
+enum Block {
+    Struct,
+    Map,
+    Seq,
+    Tuple,
+}
+
+/// A block of `{:#?}` output which has been opened, but not yet closed.
+struct Frame {
+    path: String,
+    block: Block,
+    // The number of elements seen so far, for sequences and tuples
+    count: usize,
+}
+
+impl Frame {
+    /// The path of a line within this block, any field name, and the start of its value.
+    fn child<'a>(&mut self, content: &'a str) -> (String, Option<&'a str>, usize) {
+        match self.block {
+            Block::Struct | Block::Map => match content.find(": ") {
+                Some(end) if self.block == Block::Struct => {
+                    let name = &content[..end];
+                    (format!("{}.{}", self.path, name), Some(name), end + 2)
+                }
+                Some(end) => (format!("{}[{}]", self.path, &content[..end]), None, end + 2),
+                None => (self.path.clone(), None, 0),
+            },
+            Block::Seq => {
+                self.count += 1;
+                (format!("{}[{}]", self.path, self.count - 1), None, 0)
+            }
+            Block::Tuple => {
+                self.count += 1;
+                (format!("{}.{}", self.path, self.count - 1), None, 0)
+            }
+        }
+    }
+}
+
+/// The kind of block opened by a value, if any.
+fn opens(value: &str) -> Option<Block> {
+    let value = value.trim_end_matches(',');
+    if value == "{" {
+        Some(Block::Map)
+    } else if value.ends_with(" {") {
+        Some(Block::Struct)
+    } else if value.ends_with('[') {
+        Some(Block::Seq)
+    } else if value.ends_with('(') {
+        Some(Block::Tuple)
+    } else {
+        None
+    }
+}
+
+/// Whether the line content closes a block.
+fn is_close(content: &str) -> bool {
+    matches!(content.trim_end_matches(','), "}" | "]" | ")")
+}
+
+/// Apply redactions to a `{:#?}` representation.
+///
+/// The structure of the value is recovered from the indentation of each line, so that
+/// the path of each value is known. A redacted value which spans several lines is
+/// replaced by a single line.
+pub(crate) fn redact(debug: &str, redactions: &[Redaction]) -> String {
+    if redactions.is_empty() {
+        return debug.to_owned();
+    }
+    let mut output: Vec<String> = Vec::new();
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut lines = debug.split('\n');
+
+    while let Some(line) = lines.next() {
+        let indent = line.len() - line.trim_start_matches(' ').len();
+        let content = &line[indent..];
+        if is_close(content) {
+            stack.pop();
+            output.push(line.to_owned());
+            continue;
+        }
+
+        let (path, field, start) = match stack.last_mut() {
+            Some(frame) => frame.child(content),
+            None => (String::new(), None, 0),
+        };
+        let value = &content[start..];
+        let block = opens(value);
+
+        if !path.is_empty() && redactions.iter().any(|r| r.matches(&path, field)) {
+            let mut comma = value.ends_with(',');
+            if block.is_some() {
+                // Skip the rest of the value, up to the line which closes it
+                for line in lines.by_ref() {
+                    let content = &line[indent.min(line.len())..];
+                    if line.starts_with(&" ".repeat(indent)) && is_close(content) {
+                        comma = content.ends_with(',');
+                        break;
+                    }
+                }
+            }
+            output.push(format!(
+                "{}{}{}",
+                &line[..indent + start],
+                REDACTED,
+                if comma { "," } else { "" }
+            ));
+            continue;
+        }
+
+        if let Some(block) = block {
+            stack.push(Frame {
+                path,
+                block,
+                count: 0,
+            });
+        }
+        output.push(line.to_owned());
+    }
+
+    #[cfg(feature = "regex")]
+    for redaction in redactions {
+        if let Rule::Pattern(regex) = &redaction.rule {
+            for line in output.iter_mut() {
+                *line = regex.replace_all(line, REDACTED).into_owned();
+            }
+        }
+    }
+
+    output.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Item {
+        id: u32,
+        tags: Vec<&'static str>,
+    }
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Order {
+        id: u32,
+        items: Vec<Item>,
+        pair: (u32, Option<u32>),
+    }
+
+    fn order() -> String {
+        format!(
+            "{:#?}",
+            Order {
+                id: 1,
+                items: vec![
+                    Item {
+                        id: 2,
+                        tags: vec!["a"],
+                    },
+                    Item {
+                        id: 3,
+                        tags: vec![],
+                    },
+                ],
+                pair: (4, Some(5)),
+            }
+        )
+    }
+
+    #[test]
+    fn redact_field() {
+        let expected = r#"Order {
+    id: [redacted],
+    items: [
+        Item {
+            id: [redacted],
+            tags: [
+                "a",
+            ],
+        },
+        Item {
+            id: [redacted],
+            tags: [],
+        },
+    ],
+    pair: (
+        4,
+        Some(
+            5,
+        ),
+    ),
+}"#;
+        assert_eq!(redact(&order(), &["id".into()]), expected);
+    }
+
+    #[test]
+    fn redact_path() {
+        let expected = r#"Order {
+    id: 1,
+    items: [
+        Item {
+            id: 2,
+            tags: [redacted],
+        },
+        Item {
+            id: 3,
+            tags: [redacted],
+        },
+    ],
+    pair: (
+        4,
+        [redacted],
+    ),
+}"#;
+        let redactions = [".items[*].tags".into(), Redaction::path(".pair.1")];
+        assert_eq!(redact(&order(), &redactions), expected);
+    }
+
+    #[test]
+    fn redact_nothing() {
+        assert_eq!(redact(&order(), &[]), order());
+        assert_eq!(redact(&order(), &[".missing".into()]), order());
+    }
+
+    #[test]
+    fn path_matches_wildcard() {
+        assert!(path_matches(".a[*].b", ".a[10].b"));
+        assert!(path_matches(".a[*]", ".a[\"key\"]"));
+        assert!(!path_matches(".a[*].b", ".a.b"));
+        assert!(!path_matches(".a", ".ab"));
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn redact_pattern() {
+        let regex = ::regex::Regex::new(r"0x[0-9a-f]+").unwrap();
+        assert_eq!(
+            redact("Foo {\n    ptr: 0x7ffd4c1a,\n}", &[regex.into()]),
+            "Foo {\n    ptr: [redacted],\n}"
+        );
+    }
+}
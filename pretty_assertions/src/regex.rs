@@ -0,0 +1,108 @@
+use crate::printer;
+use ::regex::Regex;
+use core::fmt::{self, Display};
+
+/// A comparison of a string against a regular expression.
+///
+/// The string is displayed as-is, resulting in multi-line output for multiline strings.
+///
+/// ```
+/// use pretty_assertions::RegexComparison;
+/// use regex::Regex;
+///
+/// let pattern = Regex::new(r"^\d+ INFO .*$").unwrap();
+/// print!("{}", RegexComparison::new("1 INFO ok\n2 DEBUG no", &pattern));
+/// ```
+///
+/// The pattern is matched against the whole string, as by [`Regex::is_match`], so `^` and
+/// `$` match only at the start and end of the string, unless multi-line mode is enabled
+/// with `(?m)`.
+///
+/// ## Line-anchored patterns
+///
+/// If the pattern starts with `^` and ends with `$` (optionally preceded by an inline
+/// flag group, such as `(?m)`), each line of the string is also matched individually
+/// when displayed, and lines which do not match are highlighted. This only changes how
+/// the string is displayed, and not whether it matches.
+///
+/// # Features
+///
+/// Requires the `regex` feature to be enabled.
+pub struct RegexComparison<'a, TText>
+where
+    TText: ?Sized,
+{
+    text: &'a TText,
+    regex: &'a Regex,
+}
+
+impl<'a, TText> RegexComparison<'a, TText>
+where
+    TText: AsRef<str> + ?Sized,
+{
+    /// Store a value and pattern to be compared in future.
+    ///
+    /// Expensive matching is deferred until calling `Display::fmt`.
+    pub fn new(text: &'a TText, regex: &'a Regex) -> RegexComparison<'a, TText> {
+        RegexComparison { text, regex }
+    }
+
+    /// Whether the pattern matches somewhere in the string, as by [`Regex::is_match`].
+    pub fn is_match(&self) -> bool {
+        self.regex.is_match(self.text.as_ref())
+    }
+}
+
+impl<TText> Display for RegexComparison<'_, TText>
+where
+    TText: AsRef<str> + ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Pattern: {}", self.regex.as_str())?;
+        writeln!(f)?;
+        let text = self.text.as_ref();
+        // Highlighting lines only helps if some line does not match on its own
+        if is_line_anchored(self.regex.as_str())
+            && !text.lines().all(|line| self.regex.is_match(line))
+        {
+            printer::write_match_header(f)?;
+            printer::write_matched_lines(f, text, |line| self.regex.is_match(line))
+        } else {
+            writeln!(f, "Text:")?;
+            writeln!(f, "{}", text)
+        }
+    }
+}
+
+/// Whether the pattern is anchored to both the start and end of a line.
+fn is_line_anchored(pattern: &str) -> bool {
+    // Skip a leading inline flag group, such as `(?m)` or `(?mx)`
+    let pattern = match pattern.strip_prefix("(?") {
+        Some(rest) => match rest.find(')') {
+            Some(end) if rest[..end].chars().all(char::is_alphabetic) => &rest[end + 1..],
+            _ => pattern,
+        },
+        None => pattern,
+    };
+    pattern.starts_with('^') && pattern.ends_with('$') && !pattern.ends_with("\\$")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn is_line_anchored_plain() {
+        assert!(is_line_anchored(r"^\d+$"));
+        assert!(!is_line_anchored(r"\d+$"));
+        assert!(!is_line_anchored(r"^\d+"));
+        assert!(!is_line_anchored(r"^\d+\$"));
+    }
+
+    #[test]
+    fn is_line_anchored_flags() {
+        assert!(is_line_anchored(r"(?m)^\d+$"));
+        assert!(is_line_anchored(r"(?mx)^\d+$"));
+        assert!(!is_line_anchored(r"(?:a)^\d+$"));
+    }
+}
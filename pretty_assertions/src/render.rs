@@ -0,0 +1,112 @@
+use crate::model::Diff;
+use crate::printer;
+use core::fmt;
+
+/// A way of presenting a [`Diff`] as text.
+///
+/// The renderers provided by this crate present the same output as the printed diffs.
+/// Other presentations can be built on the same diff by implementing this trait:
+///
+/// ```
+/// use pretty_assertions::{Diff, LineKind, Renderer};
+/// use std::fmt;
+///
+/// /// Lists the numbers of the lines which only appear on the right.
+/// struct Insertions;
+///
+/// impl Renderer for Insertions {
+///     fn render(&self, f: &mut dyn fmt::Write, diff: &Diff) -> fmt::Result {
+///         for hunk in &diff.hunks {
+///             for line in &hunk.lines {
+///                 if line.kind == LineKind::Insert {
+///                     writeln!(f, "{}", line.right_line.unwrap())?;
+///                 }
+///             }
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// let diff = Diff::new("a\nb\n", "a\nx\nb\ny\n");
+/// assert_eq!(diff.render(&Insertions), "2\n4\n");
+/// ```
+pub trait Renderer {
+    /// Write the diff.
+    fn render(&self, f: &mut dyn fmt::Write, diff: &Diff) -> fmt::Result;
+}
+
+/// Renders a diff in a pretty, colorised manner, as shown by failed assertions.
+///
+/// Where a single line is replaced by another, the parts of each which differ are
+/// highlighted. Unless the diff is a single hunk starting at the first lines, each hunk
+/// starts with the range of lines it covers.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PrettyRenderer;
+
+impl Renderer for PrettyRenderer {
+    fn render(&self, mut f: &mut dyn fmt::Write, diff: &Diff) -> fmt::Result {
+        printer::write_pretty_model(&mut f, diff)
+    }
+}
+
+/// Renders a diff as a plain unified diff, in the format of `diff -u`.
+///
+/// The sides are named by the labels of the diff. Nothing is written if there are no hunks.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct UnifiedRenderer;
+
+impl Renderer for UnifiedRenderer {
+    fn render(&self, mut f: &mut dyn fmt::Write, diff: &Diff) -> fmt::Result {
+        printer::write_unified_model(&mut f, diff)
+    }
+}
+
+/// Renders a diff as self-contained HTML, as a table with a row for each line.
+///
+/// Where a single line is replaced by another, the parts of each which differ are marked
+/// with `<del>` and `<ins>`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HtmlRenderer;
+
+impl Renderer for HtmlRenderer {
+    fn render(&self, mut f: &mut dyn fmt::Write, diff: &Diff) -> fmt::Result {
+        printer::write_html_model(&mut f, diff)
+    }
+}
+
+/// Renders a diff as a unified diff in a fenced Markdown code block.
+///
+/// The block is marked as `diff`, which GitHub colors natively, so this is suited to pull
+/// request comments and job summaries. Long diffs can be collapsed in a `<details>`
+/// section, which is summarized by the number of lines removed and added.
+///
+/// ```
+/// use pretty_assertions::{Comparison, MarkdownRenderer};
+///
+/// let diff = Comparison::new(&[1, 2], &[1, 3]).diff();
+/// let markdown = diff.render(&MarkdownRenderer::new().collapse_after(50));
+/// assert!(markdown.starts_with("```diff\n--- left\n+++ right\n"));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MarkdownRenderer {
+    collapse_after: Option<usize>,
+}
+
+impl MarkdownRenderer {
+    /// Create a renderer which never collapses diffs.
+    pub fn new() -> MarkdownRenderer {
+        MarkdownRenderer::default()
+    }
+
+    /// Collapse diffs of more than the given number of lines in a `<details>` section.
+    pub fn collapse_after(mut self, lines: usize) -> MarkdownRenderer {
+        self.collapse_after = Some(lines);
+        self
+    }
+}
+
+impl Renderer for MarkdownRenderer {
+    fn render(&self, mut f: &mut dyn fmt::Write, diff: &Diff) -> fmt::Result {
+        printer::write_markdown_model(&mut f, diff, self.collapse_after)
+    }
+}
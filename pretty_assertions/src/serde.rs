@@ -0,0 +1,648 @@
+use crate::{compact, printer};
+use ::serde::ser::{self, Serialize};
+#[cfg(feature = "alloc")]
+use alloc::{
+    borrow::ToOwned,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::cmp::Ordering;
+use core::fmt::{self, Display};
+
+/// A comparison of two values, using their [`Serialize`] implementations.
+///
+/// In contrast to [`Comparison`](crate::Comparison), which uses the [`core::fmt::Debug`]
+/// representation, `SerdeComparison` renders each value through `serde` into a stable,
+/// pretty text format. This is independent of any hand-written `Debug` implementations.
+///
+/// The format is similar to pretty `{:#?}` output, except the entries of every map are
+/// sorted by key, so that the output of a `HashMap` is deterministic.
+///
+/// ```
+/// use pretty_assertions::SerdeComparison;
+/// use std::collections::HashMap;
+///
+/// let left: HashMap<&str, u32> = [("a", 1), ("b", 2)].iter().copied().collect();
+/// let right: HashMap<&str, u32> = [("b", 3), ("a", 1)].iter().copied().collect();
+/// print!("{}", SerdeComparison::new(&left, &right));
+/// ```
+///
+/// Sets are serialized as sequences, so the elements of a `HashSet` are in no particular
+/// order. Use [`sort_sequences`](SerdeComparison::sort_sequences) to sort the elements
+/// of every sequence, so that the output is deterministic.
+///
+/// The values may have different types, although in practice they are usually the same.
+///
+/// # Features
+///
+/// Requires the `serde` feature to be enabled.
+pub struct SerdeComparison<'a, TLeft, TRight>
+where
+    TLeft: ?Sized,
+    TRight: ?Sized,
+{
+    left: &'a TLeft,
+    right: &'a TRight,
+    sort_sequences: bool,
+}
+
+impl<'a, TLeft, TRight> SerdeComparison<'a, TLeft, TRight>
+where
+    TLeft: Serialize + ?Sized,
+    TRight: Serialize + ?Sized,
+{
+    /// Store two values to be compared in future.
+    ///
+    /// Expensive diffing is deferred until calling `Display::fmt`.
+    pub fn new(left: &'a TLeft, right: &'a TRight) -> SerdeComparison<'a, TLeft, TRight> {
+        SerdeComparison {
+            left,
+            right,
+            sort_sequences: false,
+        }
+    }
+
+    /// Sort the elements of every sequence by their rendered text, as for the keys of a map.
+    ///
+    /// This makes the output of a `HashSet` deterministic, although the order of a `Vec` is
+    /// then not shown.
+    ///
+    /// ```
+    /// use pretty_assertions::SerdeComparison;
+    /// use std::collections::HashSet;
+    ///
+    /// let left: HashSet<u32> = (0..10).collect();
+    /// let right: HashSet<u32> = (1..11).collect();
+    /// print!("{}", SerdeComparison::new(&left, &right).sort_sequences());
+    /// ```
+    pub fn sort_sequences(mut self) -> SerdeComparison<'a, TLeft, TRight> {
+        self.sort_sequences = true;
+        self
+    }
+}
+
+impl<TLeft, TRight> Display for SerdeComparison<'_, TLeft, TRight>
+where
+    TLeft: Serialize + ?Sized,
+    TRight: Serialize + ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let serializer = NodeSerializer {
+            sort_sequences: self.sort_sequences,
+        };
+        let (left, right) = compact::compact_sequences(
+            &to_pretty_string(self.left, serializer),
+            &to_pretty_string(self.right, serializer),
+        );
+        printer::write_header(f)?;
+        printer::write_lines(f, &left, &right)
+    }
+}
+
+/// Render a value in the pretty format used by [`SerdeComparison`].
+///
+/// If the value fails to serialize, the error is rendered in place of the value.
+fn to_pretty_string<T: Serialize + ?Sized>(value: &T, serializer: NodeSerializer) -> String {
+    match value.serialize(serializer) {
+        Ok(node) => node.render(),
+        Err(err) => format!("<failed to serialize: {}>", err),
+    }
+}
+
+/// The indent added for each level of nesting.
+const INDENT: usize = 4;
+
+/// A serialized value, ready to be rendered.
+enum Node {
+    /// A value written on a single line, such as a number or string.
+    Scalar(String),
+    /// A value containing other values, such as a sequence or struct.
+    Compound {
+        // The text before the opening bracket, such as a struct or variant name
+        prefix: String,
+        open: char,
+        close: char,
+        // The rendered key of each entry, for maps and structs, and its value
+        entries: Vec<(Option<String>, Node)>,
+    },
+}
+
+impl Node {
+    fn compound(prefix: &str, open: char, close: char) -> Node {
+        Node::Compound {
+            prefix: prefix.to_owned(),
+            open,
+            close,
+            entries: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, key: Option<String>, value: Node) {
+        if let Node::Compound { entries, .. } = self {
+            entries.push((key, value));
+        }
+    }
+
+    /// Write the node, with any nested lines indented by the given amount.
+    fn write(&self, output: &mut String, indent: usize) {
+        match self {
+            Node::Scalar(value) => output.push_str(value),
+            Node::Compound {
+                prefix,
+                open,
+                close,
+                entries,
+            } => {
+                output.push_str(prefix);
+                output.push(*open);
+                if entries.is_empty() {
+                    output.push(*close);
+                    return;
+                }
+                output.push('\n');
+                for (key, value) in entries {
+                    output.push_str(&" ".repeat(indent + INDENT));
+                    if let Some(key) = key {
+                        output.push_str(key);
+                        output.push_str(": ");
+                    }
+                    value.write(output, indent + INDENT);
+                    output.push_str(",\n");
+                }
+                output.push_str(&" ".repeat(indent));
+                output.push(*close);
+            }
+        }
+    }
+
+    fn render(&self) -> String {
+        let mut output = String::new();
+        self.write(&mut output, 0);
+        output
+    }
+}
+
+/// Order map keys, comparing integers by value and anything else as text.
+fn compare_keys(left: &str, right: &str) -> Ordering {
+    match (left.parse::<i128>(), right.parse::<i128>()) {
+        (Ok(left), Ok(right)) => left.cmp(&right),
+        _ => left.cmp(right),
+    }
+}
+
+/// An error raised by a [`Serialize`] implementation.
+#[derive(Debug)]
+struct Error(String);
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl ser::StdError for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+/// Serializes a value into a [`Node`].
+#[derive(Clone, Copy, Default)]
+struct NodeSerializer {
+    // Whether to sort the elements of sequences
+    sort_sequences: bool,
+}
+
+/// Collects the entries of a compound value.
+struct CompoundSerializer {
+    serializer: NodeSerializer,
+    node: Node,
+    // For maps, the rendered key waiting for its value
+    key: Option<String>,
+    sort: bool,
+    sort_values: bool,
+}
+
+impl CompoundSerializer {
+    fn new(serializer: NodeSerializer, node: Node) -> Self {
+        CompoundSerializer {
+            serializer,
+            node,
+            key: None,
+            sort: false,
+            sort_values: false,
+        }
+    }
+
+    fn push<T: Serialize + ?Sized>(&mut self, key: Option<String>, value: &T) -> Result<(), Error> {
+        let value = value.serialize(self.serializer)?;
+        self.node.push(key, value);
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<Node, Error> {
+        if let Node::Compound { entries, .. } = &mut self.node {
+            if self.sort {
+                entries.sort_by(|a, b| match (&a.0, &b.0) {
+                    (Some(a), Some(b)) => compare_keys(a, b),
+                    _ => Ordering::Equal,
+                });
+            } else if self.sort_values {
+                entries.sort_by(|a, b| compare_keys(&a.1.render(), &b.1.render()));
+            }
+        }
+        Ok(self.node)
+    }
+}
+
+macro_rules! serialize_display {
+    ($($method:ident: $ty:ty),*) => {
+        $(
+            fn $method(self, value: $ty) -> Result<Node, Error> {
+                Ok(Node::Scalar(value.to_string()))
+            }
+        )*
+    };
+}
+
+macro_rules! serialize_debug {
+    ($($method:ident: $ty:ty),*) => {
+        $(
+            fn $method(self, value: $ty) -> Result<Node, Error> {
+                Ok(Node::Scalar(format!("{:?}", value)))
+            }
+        )*
+    };
+}
+
+impl ser::Serializer for NodeSerializer {
+    type Ok = Node;
+    type Error = Error;
+    type SerializeSeq = CompoundSerializer;
+    type SerializeTuple = CompoundSerializer;
+    type SerializeTupleStruct = CompoundSerializer;
+    type SerializeTupleVariant = CompoundSerializer;
+    type SerializeMap = CompoundSerializer;
+    type SerializeStruct = CompoundSerializer;
+    type SerializeStructVariant = CompoundSerializer;
+
+    serialize_display!(
+        serialize_bool: bool,
+        serialize_i8: i8,
+        serialize_i16: i16,
+        serialize_i32: i32,
+        serialize_i64: i64,
+        serialize_i128: i128,
+        serialize_u8: u8,
+        serialize_u16: u16,
+        serialize_u32: u32,
+        serialize_u64: u64,
+        serialize_u128: u128
+    );
+
+    serialize_debug!(
+        serialize_f32: f32,
+        serialize_f64: f64,
+        serialize_char: char,
+        serialize_str: &str
+    );
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<Node, Error> {
+        let mut node = Node::compound("", '[', ']');
+        for byte in value {
+            node.push(None, Node::Scalar(byte.to_string()));
+        }
+        Ok(node)
+    }
+
+    fn serialize_none(self) -> Result<Node, Error> {
+        Ok(Node::Scalar("None".to_owned()))
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Node, Error> {
+        let mut node = Node::compound("Some", '(', ')');
+        node.push(None, value.serialize(self)?);
+        Ok(node)
+    }
+
+    fn serialize_unit(self) -> Result<Node, Error> {
+        Ok(Node::Scalar("()".to_owned()))
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<Node, Error> {
+        Ok(Node::Scalar(name.to_owned()))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<Node, Error> {
+        Ok(Node::Scalar(variant.to_owned()))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<Node, Error> {
+        let mut node = Node::compound(name, '(', ')');
+        node.push(None, value.serialize(self)?);
+        Ok(node)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Node, Error> {
+        let mut node = Node::compound(variant, '(', ')');
+        node.push(None, value.serialize(self)?);
+        Ok(node)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<CompoundSerializer, Error> {
+        let mut serializer = CompoundSerializer::new(self, Node::compound("", '[', ']'));
+        serializer.sort_values = self.sort_sequences;
+        Ok(serializer)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<CompoundSerializer, Error> {
+        Ok(CompoundSerializer::new(self, Node::compound("", '(', ')')))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        _len: usize,
+    ) -> Result<CompoundSerializer, Error> {
+        Ok(CompoundSerializer::new(
+            self,
+            Node::compound(name, '(', ')'),
+        ))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<CompoundSerializer, Error> {
+        Ok(CompoundSerializer::new(
+            self,
+            Node::compound(variant, '(', ')'),
+        ))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<CompoundSerializer, Error> {
+        let mut serializer = CompoundSerializer::new(self, Node::compound("", '{', '}'));
+        serializer.sort = true;
+        Ok(serializer)
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        _len: usize,
+    ) -> Result<CompoundSerializer, Error> {
+        let prefix = format!("{} ", name);
+        Ok(CompoundSerializer::new(
+            self,
+            Node::compound(&prefix, '{', '}'),
+        ))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<CompoundSerializer, Error> {
+        let prefix = format!("{} ", variant);
+        Ok(CompoundSerializer::new(
+            self,
+            Node::compound(&prefix, '{', '}'),
+        ))
+    }
+}
+
+impl ser::SerializeSeq for CompoundSerializer {
+    type Ok = Node;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(None, value)
+    }
+
+    fn end(self) -> Result<Node, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for CompoundSerializer {
+    type Ok = Node;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(None, value)
+    }
+
+    fn end(self) -> Result<Node, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for CompoundSerializer {
+    type Ok = Node;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(None, value)
+    }
+
+    fn end(self) -> Result<Node, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for CompoundSerializer {
+    type Ok = Node;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(None, value)
+    }
+
+    fn end(self) -> Result<Node, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeMap for CompoundSerializer {
+    type Ok = Node;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        self.key = Some(key.serialize(self.serializer)?.render());
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self.key.take();
+        self.push(key, value)
+    }
+
+    fn end(self) -> Result<Node, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for CompoundSerializer {
+    type Ok = Node;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.push(Some(key.to_owned()), value)
+    }
+
+    fn end(self) -> Result<Node, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for CompoundSerializer {
+    type Ok = Node;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.push(Some(key.to_owned()), value)
+    }
+
+    fn end(self) -> Result<Node, Error> {
+        self.finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::serde::Serialize;
+
+    #[derive(Serialize)]
+    struct Unit;
+
+    #[derive(Serialize)]
+    struct Newtype(u32);
+
+    #[derive(Serialize)]
+    enum Variant {
+        Unit,
+        Tuple(u32, char),
+        Struct { a: Option<f64> },
+    }
+
+    /// Serializes as a map, with the entries in the order given.
+    struct UnsortedMap(Vec<(u32, &'static str)>);
+
+    impl Serialize for UnsortedMap {
+        fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_map(self.0.iter().copied())
+        }
+    }
+
+    #[derive(Serialize)]
+    struct Foo {
+        unit: Unit,
+        newtype: Newtype,
+        variants: Vec<Variant>,
+        map: UnsortedMap,
+        empty: Vec<()>,
+    }
+
+    #[test]
+    fn to_pretty_string_nested() {
+        let value = Foo {
+            unit: Unit,
+            newtype: Newtype(1),
+            variants: vec![
+                Variant::Unit,
+                Variant::Tuple(2, 'x'),
+                Variant::Struct { a: Some(1.0) },
+            ],
+            map: UnsortedMap(vec![(10, "ten"), (9, "nine"), (100, "hundred")]),
+            empty: Vec::new(),
+        };
+        let expected = r#"Foo {
+    unit: Unit,
+    newtype: Newtype(
+        1,
+    ),
+    variants: [
+        Unit,
+        Tuple(
+            2,
+            'x',
+        ),
+        Struct {
+            a: Some(
+                1.0,
+            ),
+        },
+    ],
+    map: {
+        9: "nine",
+        10: "ten",
+        100: "hundred",
+    },
+    empty: [],
+}"#;
+        assert_eq!(
+            to_pretty_string(&value, NodeSerializer::default()),
+            expected
+        );
+    }
+
+    #[test]
+    fn to_pretty_string_error() {
+        struct Failing;
+        impl Serialize for Failing {
+            fn serialize<S: ::serde::Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+                Err(::serde::ser::Error::custom("no thanks"))
+            }
+        }
+        assert_eq!(
+            to_pretty_string(&Failing, NodeSerializer::default()),
+            "<failed to serialize: no thanks>"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn to_pretty_string_sort_sequences() {
+        let value: std::collections::HashSet<u32> = (0..12).rev().collect();
+        let expected = format!(
+            "[\n{}]",
+            (0..12).map(|n| format!("    {},\n", n)).collect::<String>()
+        );
+        let serializer = NodeSerializer {
+            sort_sequences: true,
+        };
+        assert_eq!(to_pretty_string(&value, serializer), expected);
+    }
+}
@@ -0,0 +1,210 @@
+use crate::StrComparison;
+use core::fmt::{self, Display};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// The environment variable which enables update mode, when set to `1`.
+pub(crate) const UPDATE_VAR: &str = "PRETTY_ASSERTIONS_UPDATE";
+
+/// Whether assertions should update their expected values, rather than failing.
+pub(crate) fn update_mode() -> bool {
+    std::env::var(UPDATE_VAR).as_deref() == Ok("1")
+}
+
+/// The name of a snapshot, derived from the path of the function it is taken in.
+///
+/// The path is given as the type name of an item declared in the function, such as
+/// `my_crate::tests::renders::f`, which becomes `my_crate__tests__renders`. Any closures
+/// the item is nested in, such as the body of an `async fn`, are skipped.
+fn snapshot_name(function: &str) -> String {
+    let mut segments: Vec<&str> = function.split("::").collect();
+    segments.pop();
+    while segments.last() == Some(&"{{closure}}") {
+        segments.pop();
+    }
+    segments.join("__")
+}
+
+thread_local! {
+    /// The number of snapshots taken in each function on this thread.
+    ///
+    /// Each test runs on its own thread, so snapshots are numbered from the start of a test.
+    static TAKEN: RefCell<HashMap<String, usize>> = RefCell::new(HashMap::new());
+}
+
+/// The file of the next snapshot taken in a function, relative to the manifest directory.
+///
+/// The first snapshot taken in a function on this thread is named after the function, and
+/// each one after it is numbered, such as `my_crate__tests__renders-2.snap`.
+fn snapshot_file(function: &str) -> String {
+    let name = snapshot_name(function);
+    let count = TAKEN.with(|taken| {
+        let mut taken = taken.borrow_mut();
+        let count = taken.entry(name.clone()).or_insert(0);
+        *count += 1;
+        *count
+    });
+    match count {
+        1 => format!("snapshots/{}.snap", name),
+        _ => format!("snapshots/{}-{}.snap", name, count),
+    }
+}
+
+/// Read the text of a snapshot file, if it exists.
+///
+/// Line endings are normalized, and the newline at the end of the file is not included.
+fn read_snapshot(path: &Path) -> Option<String> {
+    let text = fs::read_to_string(path).ok()?.replace("\r\n", "\n");
+    Some(match text.strip_suffix('\n') {
+        Some(text) => text.to_owned(),
+        None => text,
+    })
+}
+
+/// Write a file in update mode, creating its directory if needed.
+pub(crate) fn write_file(path: &Path, contents: &str) {
+    let written = match path.parent() {
+        Some(parent) => fs::create_dir_all(parent),
+        None => Ok(()),
+    }
+    .and_then(|_| fs::write(path, contents));
+    if let Err(err) = written {
+        panic!("failed to write {}: {}", path.display(), err);
+    }
+}
+
+/// Compare a value with the snapshot stored in the given file.
+///
+/// In update mode, the file is rewritten with the actual value if it differs, and the
+/// comparison always succeeds.
+fn check(root: &Path, file: &str, actual: &str, update: bool) -> Result<(), SnapshotMismatch> {
+    let expected = read_snapshot(&root.join(file));
+    if expected.as_deref() == Some(actual) {
+        return Ok(());
+    }
+    if update {
+        write_file(&root.join(file), &format!("{}\n", actual));
+        return Ok(());
+    }
+    Err(SnapshotMismatch {
+        file: file.to_owned(),
+        expected,
+        actual: actual.to_owned(),
+    })
+}
+
+/// Compare a value with its snapshot, stored under `snapshots/` in the manifest directory.
+///
+/// Used by the expansion of [`assert_snapshot!`](crate::assert_snapshot).
+pub fn assert_snapshot(
+    manifest_dir: &str,
+    function: &str,
+    actual: &str,
+) -> Result<(), SnapshotMismatch> {
+    let file = snapshot_file(function);
+    check(Path::new(manifest_dir), &file, actual, update_mode())
+}
+
+/// A value which did not match its snapshot.
+///
+/// It displays as a diff from the snapshot to the value, with the path of the snapshot
+/// relative to the manifest directory.
+#[derive(Debug)]
+pub struct SnapshotMismatch {
+    file: String,
+    expected: Option<String>,
+    actual: String,
+}
+
+impl Display for SnapshotMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.expected {
+            Some(_) => writeln!(f, "Snapshot {} does not match.", self.file)?,
+            None => writeln!(f, "Snapshot {} does not exist.", self.file)?,
+        }
+        writeln!(f, "Set {}=1 to update it.", UPDATE_VAR)?;
+        writeln!(f)?;
+        let expected = self.expected.as_deref().unwrap_or_default();
+        Display::fmt(&StrComparison::new(expected, &self.actual), f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// A temporary directory, removed when dropped.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> TempDir {
+            let path = std::env::temp_dir().join(format!(
+                "pretty_assertions-{}-{}",
+                name,
+                std::process::id()
+            ));
+            let _ = fs::remove_dir_all(&path);
+            TempDir(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn snapshot_name_from_function() {
+        assert_eq!(
+            snapshot_name("my_crate::tests::renders::f"),
+            "my_crate__tests__renders"
+        );
+        assert_eq!(
+            snapshot_name("my_crate::tests::renders::{{closure}}::f"),
+            "my_crate__tests__renders"
+        );
+    }
+
+    #[test]
+    fn snapshot_file_numbered() {
+        let function = "my_crate::tests::snapshot_file_numbered::f";
+        assert_eq!(
+            snapshot_file(function),
+            "snapshots/my_crate__tests__snapshot_file_numbered.snap"
+        );
+        assert_eq!(
+            snapshot_file(function),
+            "snapshots/my_crate__tests__snapshot_file_numbered-2.snap"
+        );
+        assert_eq!(
+            snapshot_file("my_crate::tests::other::f"),
+            "snapshots/my_crate__tests__other.snap"
+        );
+    }
+
+    #[test]
+    fn check_missing() {
+        let dir = TempDir::new("check_missing");
+        let mismatch = check(&dir.0, "snapshots/a.snap", "value", false).unwrap_err();
+        assert_eq!(mismatch.expected, None);
+        assert!(!dir.0.exists());
+    }
+
+    #[test]
+    fn check_update() {
+        let dir = TempDir::new("check_update");
+        check(&dir.0, "snapshots/a.snap", "one\ntwo", true).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.0.join("snapshots/a.snap")).unwrap(),
+            "one\ntwo\n"
+        );
+        assert!(check(&dir.0, "snapshots/a.snap", "one\ntwo", false).is_ok());
+
+        let mismatch = check(&dir.0, "snapshots/a.snap", "one\nthree", false).unwrap_err();
+        assert_eq!(mismatch.expected.as_deref(), Some("one\ntwo"));
+    }
+}
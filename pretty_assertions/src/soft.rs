@@ -0,0 +1,127 @@
+use crate::Mismatch;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt::{self, Display};
+use core::panic::Location;
+
+/// A scope which collects failed assertions, and reports them all together.
+///
+/// Failures are recorded with [`soft_assert_eq!`](crate::soft_assert_eq!), or by passing
+/// the result of [`check_eq!`](crate::check_eq!) to [`SoftAssertions::record`]. Execution continues after each failure.
+///
+/// When the scope is finished or dropped, it panics once with a numbered list of every
+/// diff recorded, along with the location of each assertion.
+///
+/// ```should_panic
+/// use pretty_assertions::{soft_assert_eq, SoftAssertions};
+///
+/// let mut soft = SoftAssertions::new();
+/// soft_assert_eq!(soft, 1 + 1, 2);
+/// soft_assert_eq!(soft, 1 + 1, 3, "we are testing addition");
+/// soft_assert_eq!(soft, 1 + 2, 4);
+/// soft.finish(); // panics, reporting two failures
+/// ```
+#[derive(Default)]
+pub struct SoftAssertions {
+    failures: Vec<SoftFailure>,
+}
+
+/// A failure recorded by [`SoftAssertions`].
+struct SoftFailure {
+    location: &'static Location<'static>,
+    mismatch: Mismatch,
+}
+
+impl SoftAssertions {
+    /// Start collecting failed assertions.
+    pub fn new() -> SoftAssertions {
+        SoftAssertions::default()
+    }
+
+    /// Record the result of a check, such as [`check_eq!`](crate::check_eq!).
+    ///
+    /// If the check failed, the location of the caller is stored alongside the mismatch.
+    /// Returns `true` if the check passed.
+    #[track_caller]
+    pub fn record(&mut self, result: Result<(), Mismatch>) -> bool {
+        match result {
+            Ok(()) => true,
+            Err(mismatch) => {
+                self.failures.push(SoftFailure {
+                    location: Location::caller(),
+                    mismatch,
+                });
+                false
+            }
+        }
+    }
+
+    /// The number of failures recorded so far.
+    pub fn len(&self) -> usize {
+        self.failures.len()
+    }
+
+    /// Whether no failures have been recorded so far.
+    pub fn is_empty(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Panic if any failures have been recorded, reporting all of them.
+    ///
+    /// This is equivalent to dropping the scope, but makes the end of it explicit, and
+    /// reports the failure at the caller.
+    #[track_caller]
+    pub fn finish(mut self) {
+        self.report()
+    }
+
+    /// Fail as any other assertion does, if any failures have been recorded.
+    ///
+    /// The failures are taken, so that the scope does not fail again when dropped.
+    #[track_caller]
+    fn report(&mut self) {
+        let failures = core::mem::take(&mut self.failures);
+        if !failures.is_empty() {
+            crate::private::fail(format_args!("{}", Report(&failures)))
+        }
+    }
+}
+
+/// The report of the failures recorded by [`SoftAssertions`].
+struct Report<'a>(&'a [SoftFailure]);
+
+impl Display for Report<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let count = self.0.len();
+        writeln!(
+            f,
+            "{} soft assertion{} failed:",
+            count,
+            if count == 1 { "" } else { "s" }
+        )?;
+        for (index, failure) in self.0.iter().enumerate() {
+            writeln!(f)?;
+            writeln!(f, "{}) {}", index + 1, failure.location)?;
+            write!(f, "{}", failure.mismatch)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for SoftAssertions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&Report(&self.failures), f)
+    }
+}
+
+impl Drop for SoftAssertions {
+    #[track_caller]
+    fn drop(&mut self) {
+        // Panicking again while unwinding would abort, hiding the original panic
+        #[cfg(feature = "std")]
+        if std::thread::panicking() {
+            return;
+        }
+        self.report()
+    }
+}
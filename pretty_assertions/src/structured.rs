@@ -0,0 +1,327 @@
+use crate::{printer, StrComparison};
+#[cfg(feature = "alloc")]
+use alloc::{borrow::ToOwned, format, string::String, vec::Vec};
+use core::cmp::Ordering;
+use core::fmt::{self, Display};
+
+/// A parsed document, reduced to the values it contains.
+///
+/// Formatting, such as quoting style, indentation and the order of keys, is discarded,
+/// so that two trees are equal if they describe the same data.
+#[derive(Debug, PartialEq)]
+pub(crate) enum Tree {
+    /// A single value, such as a number or string, rendered as text.
+    Scalar(String),
+    /// A sequence of values.
+    Seq(Vec<Tree>),
+    /// A mapping of keys to values, sorted by key.
+    Map(Vec<(String, Tree)>),
+    /// A stream of several documents, in formats which allow it.
+    #[cfg(feature = "serde_yaml")]
+    Documents(Vec<Tree>),
+}
+
+impl Tree {
+    /// Build a mapping, sorting the entries by key.
+    pub(crate) fn map<T: IntoIterator<Item = (String, Tree)>>(entries: T) -> Tree {
+        let mut entries: Vec<(String, Tree)> = entries.into_iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        Tree::Map(entries)
+    }
+
+    /// Write the tree, with any nested lines indented by the given amount.
+    fn write(&self, output: &mut String, indent: usize) {
+        let (open, close) = match self {
+            Tree::Scalar(value) => {
+                output.push_str(value);
+                return;
+            }
+            Tree::Seq(values) if values.is_empty() => {
+                output.push_str("[]");
+                return;
+            }
+            Tree::Map(entries) if entries.is_empty() => {
+                output.push_str("{}");
+                return;
+            }
+            #[cfg(feature = "serde_yaml")]
+            Tree::Documents(documents) => {
+                for (index, document) in documents.iter().enumerate() {
+                    if index > 0 {
+                        output.push_str("\n---\n");
+                    }
+                    document.write(output, indent);
+                }
+                return;
+            }
+            Tree::Seq(_) => ('[', ']'),
+            Tree::Map(_) => ('{', '}'),
+        };
+        output.push(open);
+        output.push('\n');
+        let nested = indent + 4;
+        match self {
+            Tree::Seq(values) => {
+                for value in values {
+                    output.push_str(&" ".repeat(nested));
+                    value.write(output, nested);
+                    output.push_str(",\n");
+                }
+            }
+            Tree::Map(entries) => {
+                for (key, value) in entries {
+                    output.push_str(&" ".repeat(nested));
+                    output.push_str(&path_key(key));
+                    output.push_str(": ");
+                    value.write(output, nested);
+                    output.push_str(",\n");
+                }
+            }
+            Tree::Scalar(_) => unreachable!(),
+            #[cfg(feature = "serde_yaml")]
+            Tree::Documents(_) => unreachable!(),
+        }
+        output.push_str(&" ".repeat(indent));
+        output.push(close);
+    }
+
+    /// Render the tree as pretty text, in a similar style to `{:#?}`.
+    pub(crate) fn render(&self) -> String {
+        let mut output = String::new();
+        self.write(&mut output, 0);
+        output
+    }
+}
+
+/// A value which is present on one or both sides, but not the same on each.
+struct Difference<'a> {
+    path: String,
+    left: Option<&'a Tree>,
+    right: Option<&'a Tree>,
+}
+
+/// Collect the differences between two trees, along with the path to each.
+///
+/// Mappings are compared key by key, and sequences index by index, so only the values
+/// which differ are reported, rather than the whole of any sequence or mapping.
+fn differences<'a>(path: &str, left: &'a Tree, right: &'a Tree, output: &mut Vec<Difference<'a>>) {
+    match (left, right) {
+        (Tree::Map(left), Tree::Map(right)) => {
+            let (mut left, mut right) = (left.iter().peekable(), right.iter().peekable());
+            loop {
+                let order = match (left.peek(), right.peek()) {
+                    (None, None) => break,
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (Some(l), Some(r)) => l.0.cmp(&r.0),
+                };
+                let (key, l, r) = match order {
+                    Ordering::Less => {
+                        let (key, value) = left.next().unwrap();
+                        (key, Some(value), None)
+                    }
+                    Ordering::Greater => {
+                        let (key, value) = right.next().unwrap();
+                        (key, None, Some(value))
+                    }
+                    Ordering::Equal => {
+                        let (key, l) = left.next().unwrap();
+                        let (_, r) = right.next().unwrap();
+                        (key, Some(l), Some(r))
+                    }
+                };
+                let path = if path.is_empty() {
+                    path_key(key)
+                } else {
+                    format!("{}.{}", path, path_key(key))
+                };
+                push_differences(path, l, r, output);
+            }
+        }
+        (Tree::Seq(left), Tree::Seq(right)) => {
+            for index in 0..left.len().max(right.len()) {
+                let path = format!("{}[{}]", path, index);
+                push_differences(path, left.get(index), right.get(index), output);
+            }
+        }
+        #[cfg(feature = "serde_yaml")]
+        (Tree::Documents(left), Tree::Documents(right)) => {
+            // Documents are only found at the root, and are numbered from 1 as in an editor
+            for index in 0..left.len().max(right.len()) {
+                let path = format!("(document {})", index + 1);
+                push_differences(path, left.get(index), right.get(index), output);
+            }
+        }
+        (left, right) => {
+            if left != right {
+                output.push(Difference {
+                    path: path.to_owned(),
+                    left: Some(left),
+                    right: Some(right),
+                });
+            }
+        }
+    }
+}
+
+/// Collect the differences between two values which may be missing from either side.
+fn push_differences<'a>(
+    path: String,
+    left: Option<&'a Tree>,
+    right: Option<&'a Tree>,
+    output: &mut Vec<Difference<'a>>,
+) {
+    match (left, right) {
+        (Some(left), Some(right)) => differences(&path, left, right, output),
+        (left, right) => output.push(Difference { path, left, right }),
+    }
+}
+
+/// Format a key for use in a path, quoting it unless it is a plain identifier.
+fn path_key(key: &str) -> String {
+    let plain = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-');
+    if plain {
+        key.to_owned()
+    } else {
+        format!("{:?}", key)
+    }
+}
+
+/// A document in some structured text format, and the result of parsing it.
+pub(crate) struct Document<'a> {
+    pub(crate) text: &'a str,
+    pub(crate) tree: Result<Tree, String>,
+}
+
+/// A comparison of two documents in the same structured text format.
+///
+/// Shared by the comparisons of each format, which only differ in how they parse text.
+pub(crate) struct StructuredComparison<'a> {
+    // The name of the format, used in error messages
+    pub(crate) format: &'static str,
+    pub(crate) left: Document<'a>,
+    pub(crate) right: Document<'a>,
+}
+
+impl StructuredComparison<'_> {
+    /// Whether the documents describe the same data.
+    ///
+    /// If either document fails to parse, the text of each is compared instead.
+    pub(crate) fn is_equal(&self) -> bool {
+        match (&self.left.tree, &self.right.tree) {
+            (Ok(left), Ok(right)) => left == right,
+            _ => self.left.text == self.right.text,
+        }
+    }
+}
+
+impl Display for StructuredComparison<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (left, right) = match (&self.left.tree, &self.right.tree) {
+            (Ok(left), Ok(right)) => (left, right),
+            (left, right) => {
+                for (side, tree) in &[("left", left), ("right", right)] {
+                    if let Err(err) = tree {
+                        writeln!(f, "Failed to parse {} as {}: {}", side, self.format, err)?;
+                    }
+                }
+                writeln!(f)?;
+                return Display::fmt(&StrComparison::new(self.left.text, self.right.text), f);
+            }
+        };
+
+        let mut found = Vec::new();
+        differences("", left, right, &mut found);
+        printer::write_header(f)?;
+        for difference in found {
+            let path = if difference.path.is_empty() {
+                "(root)"
+            } else {
+                &difference.path
+            };
+            writeln!(f, "{}:", path)?;
+            let render = |tree: Option<&Tree>| tree.map(Tree::render).unwrap_or_default();
+            printer::write_lines(f, &render(difference.left), &render(difference.right))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn scalar(value: &str) -> Tree {
+        Tree::Scalar(value.to_string())
+    }
+
+    fn paths(left: &Tree, right: &Tree) -> Vec<String> {
+        let mut found = Vec::new();
+        differences("", left, right, &mut found);
+        found
+            .into_iter()
+            .map(|difference| difference.path)
+            .collect()
+    }
+
+    #[test]
+    fn differences_nested() {
+        let left = Tree::map(vec![
+            ("name".to_string(), scalar("\"web\"")),
+            (
+                "ports".to_string(),
+                Tree::Seq(vec![scalar("80"), scalar("443")]),
+            ),
+            ("app.kubernetes.io/tier".to_string(), scalar("\"frontend\"")),
+        ]);
+        let right = Tree::map(vec![
+            ("ports".to_string(), Tree::Seq(vec![scalar("8080")])),
+            ("name".to_string(), scalar("\"web\"")),
+            ("replicas".to_string(), scalar("3")),
+        ]);
+        assert_eq!(
+            paths(&left, &right),
+            vec![
+                "\"app.kubernetes.io/tier\"",
+                "ports[0]",
+                "ports[1]",
+                "replicas"
+            ]
+        );
+    }
+
+    #[test]
+    fn differences_root() {
+        assert_eq!(paths(&scalar("1"), &Tree::Seq(vec![])), vec![""]);
+        assert!(paths(&scalar("1"), &scalar("1")).is_empty());
+    }
+
+    #[cfg(feature = "serde_yaml")]
+    #[test]
+    fn differences_documents() {
+        let left = Tree::Documents(vec![
+            Tree::map(vec![("a".to_string(), scalar("1"))]),
+            scalar("2"),
+        ]);
+        let right = Tree::Documents(vec![Tree::map(vec![("a".to_string(), scalar("3"))])]);
+        assert_eq!(paths(&left, &right), vec!["(document 1).a", "(document 2)"]);
+    }
+
+    #[test]
+    fn render_nested() {
+        let tree = Tree::map(vec![
+            (
+                "b".to_string(),
+                Tree::Seq(vec![scalar("1"), Tree::Map(vec![])]),
+            ),
+            ("a".to_string(), scalar("true")),
+        ]);
+        assert_eq!(
+            tree.render(),
+            "{\n    a: true,\n    b: [\n        1,\n        {},\n    ],\n}"
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use crate::model::Diff;
+use crate::printer;
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+/// Which differences between two strings are ignored by a
+/// [`StrComparison`](crate::StrComparison).
+///
+/// By default, strings are compared exactly.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct TextOptions {
+    pub(crate) ignore_case: bool,
+    pub(crate) collapse_whitespace: bool,
+    pub(crate) trim_lines: bool,
+    pub(crate) ignore_blank_lines: bool,
+}
+
+impl TextOptions {
+    /// Whether strings are compared exactly.
+    fn is_exact(&self) -> bool {
+        !(self.ignore_case
+            || self.collapse_whitespace
+            || self.trim_lines
+            || self.ignore_blank_lines)
+    }
+
+    /// Split the text into lines, dropping blank lines if they are ignored.
+    fn lines<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        text.split('\n')
+            .filter(|line| !self.ignore_blank_lines || !line.trim().is_empty())
+            .collect()
+    }
+
+    /// The form of a line which is compared, with any ignored differences removed.
+    fn key(&self, line: &str) -> String {
+        let line = if self.trim_lines { line.trim() } else { line };
+        let line = if self.collapse_whitespace {
+            collapse_whitespace(line)
+        } else {
+            line.into()
+        };
+        if self.ignore_case {
+            line.to_lowercase()
+        } else {
+            line
+        }
+    }
+
+    /// Whether two strings are equal, ignoring any differences these options allow.
+    pub(crate) fn eq(&self, left: &str, right: &str) -> bool {
+        if self.is_exact() {
+            return left == right;
+        }
+        let (left, right) = (self.lines(left), self.lines(right));
+        left.len() == right.len()
+            && left
+                .iter()
+                .zip(right.iter())
+                .all(|(left, right)| self.key(left) == self.key(right))
+    }
+
+    /// Present the diff output for two strings, ignoring any differences these options allow.
+    ///
+    /// Lines are printed as they appear in the original text.
+    pub(crate) fn write_lines<TWrite: fmt::Write>(
+        &self,
+        f: &mut TWrite,
+        left: &str,
+        right: &str,
+    ) -> fmt::Result {
+        if self.is_exact() {
+            return printer::write_lines(f, left, right);
+        }
+        printer::write_lines_by_key(f, &self.lines(left), &self.lines(right), |line| {
+            self.key(line)
+        })
+    }
+
+    /// Present a unified diff for two strings, ignoring any differences these options allow.
+    pub(crate) fn write_unified<TWrite: fmt::Write>(
+        &self,
+        f: &mut TWrite,
+        left: &str,
+        right: &str,
+    ) -> fmt::Result {
+        if self.is_exact() {
+            return printer::write_unified(f, left, right);
+        }
+        printer::write_unified_by_key(f, &self.lines(left), &self.lines(right), |line| {
+            self.key(line)
+        })
+    }
+
+    /// Diff two strings into the data model, ignoring any differences these options allow.
+    pub(crate) fn diff(&self, left: &str, right: &str) -> Diff {
+        if self.is_exact() {
+            return Diff::new(left, right);
+        }
+        printer::diff_model_by_key(&self.lines(left), &self.lines(right), |line| self.key(line))
+    }
+
+    /// Present an HTML diff for two strings, ignoring any differences these options allow.
+    pub(crate) fn write_html<TWrite: fmt::Write>(
+        &self,
+        f: &mut TWrite,
+        left: &str,
+        right: &str,
+    ) -> fmt::Result {
+        if self.is_exact() {
+            return printer::write_html(f, left, right);
+        }
+        printer::write_html_by_key(f, &self.lines(left), &self.lines(right), |line| {
+            self.key(line)
+        })
+    }
+}
+
+/// Replace each run of whitespace with a single space, keeping any leading or trailing space.
+fn collapse_whitespace(line: &str) -> String {
+    let mut output = String::with_capacity(line.len());
+    let mut in_whitespace = false;
+    for c in line.chars() {
+        if c.is_whitespace() {
+            if !in_whitespace {
+                output.push(' ');
+            }
+            in_whitespace = true;
+        } else {
+            output.push(c);
+            in_whitespace = false;
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn eq_exact() {
+        let options = TextOptions::default();
+        assert!(options.eq("a\nb", "a\nb"));
+        assert!(!options.eq("a\nb", "a\nB"));
+    }
+
+    #[test]
+    fn eq_ignore_case() {
+        let options = TextOptions {
+            ignore_case: true,
+            ..TextOptions::default()
+        };
+        assert!(options.eq("SELECT *\nFROM t", "select *\nfrom T"));
+        assert!(!options.eq("SELECT *", "SELECT  *"));
+    }
+
+    #[test]
+    fn eq_whitespace() {
+        let options = TextOptions {
+            collapse_whitespace: true,
+            ..TextOptions::default()
+        };
+        assert!(options.eq("a \t b", "a b"));
+        assert!(!options.eq(" a b", "a b"));
+
+        let options = TextOptions {
+            trim_lines: true,
+            ..TextOptions::default()
+        };
+        assert!(options.eq("  a\nb  ", "a\n\tb"));
+        assert!(!options.eq("a\n\nb", "a\nb"));
+
+        let options = TextOptions {
+            ignore_blank_lines: true,
+            ..TextOptions::default()
+        };
+        assert!(options.eq("a\n\n  \nb\n", "a\nb"));
+    }
+
+    #[test]
+    fn write_unified_ignore_case() {
+        let options = TextOptions {
+            ignore_case: true,
+            ..TextOptions::default()
+        };
+        let mut output = String::new();
+        options
+            .write_unified(&mut output, "A\nb\nc", "a\nB\nd")
+            .unwrap();
+        assert_eq!(
+            output,
+            "--- left\n+++ right\n@@ -1,3 +1,3 @@\n A\n b\n-c\n+d\n"
+        );
+    }
+}
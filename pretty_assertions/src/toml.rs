@@ -0,0 +1,106 @@
+use crate::structured::{Document, StructuredComparison, Tree};
+use ::toml::Value;
+#[cfg(feature = "alloc")]
+use alloc::string::{String, ToString};
+use core::fmt::{self, Display};
+
+/// A comparison of two TOML documents.
+///
+/// Each document is parsed, so that cosmetic differences such as quoting style,
+/// whitespace, inline tables and the order of keys are ignored. Each value which differs
+/// is reported along with its key path, such as `dependencies.serde.version`.
+///
+/// ```
+/// use pretty_assertions::TomlComparison;
+///
+/// let left = "[package]\nname = \"foo\"\nversion = \"0.1.0\"\n";
+/// let right = "package = { version = '0.2.0', name = 'foo' }\n";
+/// print!("{}", TomlComparison::new(left, right));
+/// ```
+///
+/// If either document fails to parse, the error is reported and the text of each
+/// document is compared as a [`StrComparison`](crate::StrComparison) instead.
+///
+/// # Features
+///
+/// Requires the `toml` feature to be enabled.
+pub struct TomlComparison<'a> {
+    inner: StructuredComparison<'a>,
+}
+
+impl<'a> TomlComparison<'a> {
+    /// Parse two documents to be compared in future.
+    ///
+    /// Expensive diffing is deferred until calling `Display::fmt`.
+    pub fn new(left: &'a str, right: &'a str) -> TomlComparison<'a> {
+        TomlComparison {
+            inner: StructuredComparison {
+                format: "TOML",
+                left: parse(left),
+                right: parse(right),
+            },
+        }
+    }
+
+    /// Whether the documents describe the same data.
+    ///
+    /// If either document fails to parse, the text of each is compared instead.
+    pub fn is_equal(&self) -> bool {
+        self.inner.is_equal()
+    }
+}
+
+impl Display for TomlComparison<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.inner, f)
+    }
+}
+
+fn parse(text: &str) -> Document<'_> {
+    Document {
+        text,
+        tree: ::toml::from_str(text)
+            .map(|value| to_tree(&value))
+            .map_err(|err| err.to_string().trim_end().to_string()),
+    }
+}
+
+fn to_tree(value: &Value) -> Tree {
+    match value {
+        Value::String(value) => Tree::Scalar(format!("{:?}", value)),
+        Value::Integer(value) => Tree::Scalar(value.to_string()),
+        Value::Float(value) => Tree::Scalar(format!("{:?}", value)),
+        Value::Boolean(value) => Tree::Scalar(value.to_string()),
+        Value::Datetime(value) => Tree::Scalar(value.to_string()),
+        Value::Array(values) => Tree::Seq(values.iter().map(to_tree).collect()),
+        Value::Table(table) => Tree::map(
+            table
+                .iter()
+                .map(|(key, value)| (String::from(key.as_str()), to_tree(value))),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn is_equal_ignores_formatting() {
+        let left = "[package]\nname = \"foo\"\nedition = '2018'\n\n[dependencies]\nserde = \"1\"\n";
+        let right = "dependencies = { serde = '1' }\n[package]\nedition = \"2018\"\nname = 'foo'\n";
+        assert!(TomlComparison::new(left, right).is_equal());
+    }
+
+    #[test]
+    fn is_equal_distinguishes_types() {
+        assert!(!TomlComparison::new("version = 1", "version = '1'").is_equal());
+        assert!(!TomlComparison::new("version = 1", "version = 1.0").is_equal());
+    }
+
+    #[test]
+    fn is_equal_invalid() {
+        assert!(TomlComparison::new("[", "[").is_equal());
+        assert!(!TomlComparison::new("[", "[a]").is_equal());
+    }
+}
@@ -0,0 +1,121 @@
+use crate::markup::{text_node, Document, Element, Markup, MarkupComparison, Node};
+#[cfg(feature = "alloc")]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Display};
+
+/// A comparison of two XML documents.
+///
+/// Each document is parsed and normalized before diffing: attributes are sorted by name,
+/// runs of whitespace in text are collapsed, and comments and processing instructions are
+/// dropped. Both sides are then pretty-printed with one element per line, and the lines
+/// diffed. The path of each element which differs, such as `/feed/entry[2]/title`, is
+/// listed before the diff.
+///
+/// ```
+/// use pretty_assertions::XmlComparison;
+///
+/// let left = r#"<feed><entry id="1" lang="en"><title>Hello</title></entry></feed>"#;
+/// let right = "<feed>\n  <entry lang='en' id='1'>\n    <title>Goodbye</title>\n  </entry>\n</feed>";
+/// print!("{}", XmlComparison::new(left, right));
+/// ```
+///
+/// If either document fails to parse, the error is reported and the text of each
+/// document is compared as a [`StrComparison`](crate::StrComparison) instead.
+///
+/// # Features
+///
+/// Requires the `roxmltree` feature to be enabled.
+pub struct XmlComparison<'a> {
+    inner: MarkupComparison<'a>,
+}
+
+impl<'a> XmlComparison<'a> {
+    /// Parse two documents to be compared in future.
+    ///
+    /// Expensive diffing is deferred until calling `Display::fmt`.
+    pub fn new(left: &'a str, right: &'a str) -> XmlComparison<'a> {
+        XmlComparison {
+            inner: MarkupComparison {
+                markup: Markup::Xml,
+                left: parse(left),
+                right: parse(right),
+            },
+        }
+    }
+
+    /// Whether the documents are the same once normalized.
+    ///
+    /// If either document fails to parse, the text of each is compared instead.
+    pub fn is_equal(&self) -> bool {
+        self.inner.is_equal()
+    }
+}
+
+impl Display for XmlComparison<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.inner, f)
+    }
+}
+
+fn parse(text: &str) -> Document<'_> {
+    let nodes = match roxmltree::Document::parse(text) {
+        Ok(document) => Ok(convert(document.root())),
+        Err(err) => Err(err.to_string()),
+    };
+    Document { text, nodes }
+}
+
+fn convert(parent: roxmltree::Node) -> Vec<Node> {
+    parent
+        .children()
+        .filter_map(|node| {
+            if node.is_element() {
+                Some(Node::Element(Element::new(
+                    qualified_name(node.tag_name().namespace(), node.tag_name().name()),
+                    node.attributes().map(|attribute| {
+                        let name = qualified_name(attribute.namespace(), attribute.name());
+                        (name, Some(attribute.value().to_string()))
+                    }),
+                    convert(node),
+                )))
+            } else if node.is_text() {
+                text_node(node.text()?)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// The name of an element or attribute, with its namespace URI if it has one.
+///
+/// Namespace prefixes are arbitrary, so the URI is used in place of the prefix.
+fn qualified_name(namespace: Option<&str>, name: &str) -> String {
+    match namespace {
+        Some(namespace) => format!("{{{}}}{}", namespace, name),
+        None => name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn is_equal_ignores_formatting() {
+        let left = "<?xml version=\"1.0\"?><a x=\"1\" y='2'><b>  text </b><c/></a>";
+        let right = "<a y=\"2\" x=\"1\">\n  <!-- comment -->\n  <b>text</b>\n  <c></c>\n</a>";
+        assert!(XmlComparison::new(left, right).is_equal());
+    }
+
+    #[test]
+    fn is_equal_namespaces() {
+        let left = "<p:a xmlns:p=\"urn:x\"/>";
+        let right = "<q:a xmlns:q=\"urn:x\"/>";
+        assert!(XmlComparison::new(left, right).is_equal());
+        assert!(!XmlComparison::new(left, "<a/>").is_equal());
+    }
+}
@@ -0,0 +1,153 @@
+use crate::structured::{Document, StructuredComparison, Tree};
+use ::serde::Deserialize;
+#[cfg(feature = "alloc")]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Display};
+use serde_yaml::{Deserializer, Value};
+
+/// A comparison of two YAML documents.
+///
+/// Each document is parsed, so that cosmetic differences such as quoting style,
+/// indentation and the order of keys are ignored. Each value which differs is reported
+/// along with its key path, such as `spec.template.containers[0].image`.
+///
+/// ```
+/// use pretty_assertions::YamlComparison;
+///
+/// let left = "name: web\nports: [80, 443]\n";
+/// let right = "ports:\n  - 80\n  - 8443\nname: 'web'\n";
+/// print!("{}", YamlComparison::new(left, right));
+/// ```
+///
+/// The text may hold several documents separated by `---`. If either side holds more than
+/// one, they are compared document by document, and each difference is reported along
+/// with the number of its document, such as `(document 2).spec.replicas`.
+///
+/// If either document fails to parse, the error is reported and the text of each
+/// document is compared as a [`StrComparison`](crate::StrComparison) instead.
+///
+/// # Features
+///
+/// Requires the `serde_yaml` feature to be enabled.
+pub struct YamlComparison<'a> {
+    inner: StructuredComparison<'a>,
+}
+
+impl<'a> YamlComparison<'a> {
+    /// Parse two documents to be compared in future.
+    ///
+    /// Expensive diffing is deferred until calling `Display::fmt`.
+    pub fn new(left: &'a str, right: &'a str) -> YamlComparison<'a> {
+        let (left_documents, right_documents) = (parse(left), parse(right));
+        // Compare streams of several documents document by document, even if one side
+        // holds only one
+        let several = |documents: &Result<Vec<Tree>, String>| matches!(documents, Ok(documents) if documents.len() > 1);
+        let stream = several(&left_documents) || several(&right_documents);
+        YamlComparison {
+            inner: StructuredComparison {
+                format: "YAML",
+                left: document(left, left_documents, stream),
+                right: document(right, right_documents, stream),
+            },
+        }
+    }
+
+    /// Whether the documents describe the same data.
+    ///
+    /// If either document fails to parse, the text of each is compared instead.
+    pub fn is_equal(&self) -> bool {
+        self.inner.is_equal()
+    }
+}
+
+impl Display for YamlComparison<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.inner, f)
+    }
+}
+
+/// Parse each document in the text.
+fn parse(text: &str) -> Result<Vec<Tree>, String> {
+    let mut documents = Vec::new();
+    for document in Deserializer::from_str(text) {
+        let value = Value::deserialize(document).map_err(|err| err.to_string())?;
+        documents.push(to_tree(&value));
+    }
+    Ok(documents)
+}
+
+/// The document parsed from the text, which is a stream of documents if `stream` is set.
+///
+/// Text with no documents, such as an empty string, holds a single null value.
+fn document(text: &str, documents: Result<Vec<Tree>, String>, stream: bool) -> Document<'_> {
+    let tree = documents.map(|mut documents| {
+        if stream {
+            Tree::Documents(documents)
+        } else {
+            documents
+                .pop()
+                .unwrap_or_else(|| Tree::Scalar("null".to_string()))
+        }
+    });
+    Document { text, tree }
+}
+
+fn to_tree(value: &Value) -> Tree {
+    match value {
+        Value::Null => Tree::Scalar("null".to_string()),
+        Value::Bool(value) => Tree::Scalar(value.to_string()),
+        Value::Number(value) => Tree::Scalar(value.to_string()),
+        Value::String(value) => Tree::Scalar(format!("{:?}", value)),
+        Value::Sequence(values) => Tree::Seq(values.iter().map(to_tree).collect()),
+        Value::Mapping(mapping) => Tree::map(
+            mapping
+                .iter()
+                .map(|(key, value)| (key_of(key), to_tree(value))),
+        ),
+        // Tags are kept as a mapping from the tag to the value, as for externally tagged enums
+        Value::Tagged(tagged) => Tree::map(Some((tagged.tag.to_string(), to_tree(&tagged.value)))),
+    }
+}
+
+/// The text of a mapping key, which is usually, but not always, a string.
+fn key_of(key: &Value) -> String {
+    match key {
+        Value::String(key) => key.clone(),
+        key => to_tree(key).render(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn is_equal_ignores_formatting() {
+        let left = "name: web\nlabels: {tier: frontend, app: \"web\"}\nports: [80, 443]\n";
+        let right =
+            "ports:\n  - 80\n  - 443\nlabels:\n    app: web\n    tier: 'frontend'\nname: web\n";
+        assert!(YamlComparison::new(left, right).is_equal());
+    }
+
+    #[test]
+    fn is_equal_distinguishes_types() {
+        assert!(!YamlComparison::new("replicas: 3", "replicas: '3'").is_equal());
+    }
+
+    #[test]
+    fn is_equal_documents() {
+        assert!(YamlComparison::new("a: 1\n---\nb: 2\n", "a: 1\n---\nb: 2").is_equal());
+        assert!(!YamlComparison::new("a: 1\n---\nb: 2\n", "a: 1\n---\nb: 3\n").is_equal());
+        assert!(!YamlComparison::new("a: 1\n---\nb: 2\n", "a: 1\n").is_equal());
+        assert!(YamlComparison::new("", "null").is_equal());
+    }
+
+    #[test]
+    fn is_equal_invalid() {
+        assert!(YamlComparison::new("[", "[").is_equal());
+        assert!(!YamlComparison::new("[", "[]").is_equal());
+    }
+}
@@ -0,0 +1,367 @@
+//! Tests of assertions configured by environment variables.
+//!
+//! Environment variables are shared by every test in a process, so these tests are kept
+//! apart from the other macro tests, and each holds a lock while it runs. Tests of
+//! assertions which fail against a file are also kept here, so that they are never run
+//! in update mode, which would rewrite the file.
+#![cfg(feature = "std")]
+
+use std::panic::{self, UnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::{env, fs, process};
+
+static ENVIRONMENT: Mutex<()> = Mutex::new(());
+
+/// Run a function with the given environment variables set, catching any panic.
+fn with_vars<F, R>(vars: &[(&str, &str)], f: F) -> std::thread::Result<R>
+where
+    F: FnOnce() -> R + UnwindSafe,
+{
+    let _lock = ENVIRONMENT.lock().unwrap_or_else(|err| err.into_inner());
+    // Failing assertions would otherwise update this file and its fixtures in update mode
+    env::remove_var("PRETTY_ASSERTIONS_UPDATE");
+    for (name, value) in vars {
+        env::set_var(name, value);
+    }
+    let result = panic::catch_unwind(f);
+    for (name, _) in vars {
+        env::remove_var(name);
+    }
+    result
+}
+
+/// Run a failing assertion with the given environment variables set, returning its message.
+fn failure_message<F>(vars: &[(&str, &str)], assertion: F) -> String
+where
+    F: FnOnce() + UnwindSafe,
+{
+    match with_vars(vars, assertion) {
+        Ok(()) => panic!("assertion did not fail"),
+        Err(payload) => match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(_) => panic!("panic message is not a string"),
+        },
+    }
+}
+
+#[test]
+fn artifacts() {
+    let root = env::temp_dir().join(format!("pretty_assertions-artifacts-{}", process::id()));
+    let _ = fs::remove_dir_all(&root);
+    let message = failure_message(
+        &[("PRETTY_ASSERTIONS_ARTIFACTS", root.to_str().unwrap())],
+        || {
+            pretty_assertions::assert_str_eq!("foo\nbar\n", "foo\nbaz\n");
+        },
+    );
+
+    let prefix = "Wrote left.txt, right.txt and diff.patch to ";
+    let line = message.lines().last().unwrap();
+    assert!(line.starts_with(prefix), "{}", message);
+    let dir = PathBuf::from(&line[prefix.len()..]);
+    assert!(dir.starts_with(&root));
+    let read = |name| fs::read_to_string(dir.join(name)).unwrap();
+    let files = (read("left.txt"), read("right.txt"), read("diff.patch"));
+    let _ = fs::remove_dir_all(&root);
+    assert_eq!(
+        files,
+        (
+            "foo\nbar\n".to_owned(),
+            "foo\nbaz\n".to_owned(),
+            "--- left\n+++ right\n@@ -1,2 +1,2 @@\n foo\n-bar\n+baz\n".to_owned()
+        )
+    );
+}
+
+#[test]
+fn artifacts_soft_assertions() {
+    let root = env::temp_dir().join(format!("pretty_assertions-soft-{}", process::id()));
+    let _ = fs::remove_dir_all(&root);
+    let message = failure_message(
+        &[("PRETTY_ASSERTIONS_ARTIFACTS", root.to_str().unwrap())],
+        || {
+            let mut soft = pretty_assertions::SoftAssertions::new();
+            pretty_assertions::soft_assert_eq!(soft, 1, 2);
+            pretty_assertions::soft_assert_eq!(soft, 3, 3);
+            soft.finish();
+        },
+    );
+
+    let prefix = "Wrote left.txt, right.txt and diff.patch to ";
+    let line = message.lines().last().unwrap();
+    assert!(line.starts_with(prefix), "{}", message);
+    let dir = PathBuf::from(&line[prefix.len()..]);
+    let read = |name| fs::read_to_string(dir.join(name)).unwrap();
+    let files = (read("left.txt"), read("right.txt"), read("diff.patch"));
+    let _ = fs::remove_dir_all(&root);
+    assert!(
+        message.starts_with("1 soft assertion failed:\n"),
+        "{}",
+        message
+    );
+    assert_eq!(
+        files,
+        (
+            "1".to_owned(),
+            "2".to_owned(),
+            "--- left\n+++ right\n@@ -1 +1 @@\n-1\n\\ No newline at end of file\n+2\n\\ No newline at end of file\n".to_owned()
+        )
+    );
+}
+
+#[test]
+fn artifacts_not_written_by_display() {
+    let root = env::temp_dir().join(format!("pretty_assertions-display-{}", process::id()));
+    let _lock = ENVIRONMENT.lock().unwrap_or_else(|err| err.into_inner());
+    env::set_var("PRETTY_ASSERTIONS_ARTIFACTS", &root);
+    env::set_var("PRETTY_ASSERTIONS_FORMAT", "unified");
+    let output = pretty_assertions::StrComparison::new("foo", "bar").to_string();
+    env::remove_var("PRETTY_ASSERTIONS_ARTIFACTS");
+    env::remove_var("PRETTY_ASSERTIONS_FORMAT");
+    assert!(!root.exists());
+    assert!(output.contains("\u{1b}[1mDiff\u{1b}[0m"), "{}", output);
+}
+
+#[test]
+fn format_unified() {
+    let message = failure_message(&[("PRETTY_ASSERTIONS_FORMAT", "unified")], || {
+        pretty_assertions::assert_str_eq!("foo\nbar\n", "foo\nbaz\n", "custom");
+    });
+    assert_eq!(
+        message,
+        "assertion failed: `(left == right)`: custom
+
+--- left
++++ right
+@@ -1,2 +1,2 @@
+ foo
+-bar
++baz
+
+"
+    );
+}
+
+#[test]
+fn format_markdown() {
+    let message = failure_message(&[("PRETTY_ASSERTIONS_FORMAT", "markdown")], || {
+        pretty_assertions::assert_eq!(Some(1), Some(2));
+    });
+    assert_eq!(
+        message,
+        "assertion failed: `(left == right)`
+
+```diff
+--- left
++++ right
+@@ -1,3 +1,3 @@
+ Some(
+-    1,
++    2,
+ )
+\\ No newline at end of file
+```
+
+"
+    );
+}
+
+#[test]
+fn format_markdown_collapsed() {
+    let left: String = (0..60).map(|i| format!("line {}\n", i)).collect();
+    let right: String = (0..60).map(|i| format!("row {}\n", i)).collect();
+    let message = failure_message(&[("PRETTY_ASSERTIONS_FORMAT", "markdown")], || {
+        pretty_assertions::assert_str_eq!(left, right);
+    });
+    assert!(
+        message.starts_with(
+            "assertion failed: `(left == right)`
+
+<details>
+<summary>Diff: 60 removed, 60 added</summary>
+
+```diff
+"
+        ),
+        "{}",
+        message
+    );
+    assert!(message.ends_with("```\n\n</details>\n\n"), "{}", message);
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn format_json() {
+    let message = failure_message(&[("PRETTY_ASSERTIONS_FORMAT", "json")], || {
+        pretty_assertions::assert_str_eq!("foo\nbar\n", "foo\nbaz\n");
+    });
+    let (header, json) = message.split_once("\n\n").unwrap();
+    assert_eq!(header, "assertion failed: `(left == right)`");
+    let diff: serde_json::Value = serde_json::from_str(json).unwrap();
+    let lines = &diff["hunks"][0]["lines"];
+    assert_eq!(lines[1]["kind"], "delete");
+    assert_eq!(lines[1]["text"], "bar");
+    assert_eq!(lines[2]["kind"], "insert");
+    assert_eq!(
+        lines[2]["spans"],
+        serde_json::json!([{"start": 2, "end": 3}])
+    );
+}
+
+/// Set to run [`github_actions_child`] as a test of its own, in another process.
+const CHILD_VAR: &str = "PRETTY_ASSERTIONS_TEST_CHILD";
+
+/// The line of the assertion in [`github_actions_child`].
+const CHILD_ASSERTION_LINE: u32 = line!() + 10;
+
+/// A failing assertion under GitHub Actions, run in another process by [`github_actions`].
+///
+/// The annotation is written to stderr, so can only be seen from outside the process.
+#[test]
+fn github_actions_child() {
+    if env::var_os(CHILD_VAR).is_none() {
+        return;
+    }
+    pretty_assertions::assert_str_eq!("foo\nbar\n", "foo\nbaz\n");
+}
+
+#[test]
+fn github_actions() {
+    // Output printed by a test is captured, so the annotation is only seen if written directly
+    let output = process::Command::new(env::current_exe().unwrap())
+        .args(["github_actions_child", "--exact", "--test-threads=1"])
+        .env(CHILD_VAR, "1")
+        .env("GITHUB_ACTIONS", "true")
+        .env_remove("PRETTY_ASSERTIONS_ARTIFACTS")
+        .env_remove("PRETTY_ASSERTIONS_UPDATE")
+        .env_remove("PRETTY_ASSERTIONS_FORMAT")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let expected = format!(
+        "::error file={},line={},col=5::assertion failed: `(left == right)`%0A-bar%0A+baz\n",
+        file!(),
+        CHILD_ASSERTION_LINE
+    );
+    assert!(stderr.contains(&expected), "{}", stderr);
+}
+
+#[test]
+fn assert_str_eq_literal() {
+    // A failing assertion against a literal, which would be updated in update mode
+    let message = failure_message(&[], || {
+        pretty_assertions::assert_str_eq!("foo\nbar", "foo\nbaz");
+    });
+    assert_eq!(
+        message,
+        "assertion failed: `(left == right)`
+
+\u{1b}[1mDiff\u{1b}[0m \u{1b}[31m< left\u{1b}[0m / \u{1b}[32mright >\u{1b}[0m :
+ foo
+\u{1b}[31m<ba\u{1b}[0m\u{1b}[1;48;5;52;31mr\u{1b}[0m
+\u{1b}[32m>ba\u{1b}[0m\u{1b}[1;48;5;22;32mz\u{1b}[0m
+
+"
+    );
+}
+
+#[test]
+fn update_literal() {
+    // Update a copy of a source file, at the locations its assertions would report
+    let root = env::temp_dir().join(format!("pretty_assertions-update-{}", process::id()));
+    let _ = fs::remove_dir_all(&root);
+    let source = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/inline/update.rs");
+    let original = fs::read_to_string(&source).unwrap();
+    fs::create_dir_all(root.join("tests")).unwrap();
+    fs::write(root.join("tests/update.rs"), &original).unwrap();
+
+    let manifest_dir = root.to_str();
+    let update = |vars: &[(&str, &str)], line, expected, actual| {
+        with_vars(vars, move || {
+            pretty_assertions::private::update_literal(
+                manifest_dir,
+                "tests/update.rs",
+                line,
+                5,
+                expected,
+                actual,
+            )
+        })
+    };
+    let vars = [("PRETTY_ASSERTIONS_UPDATE", "1")];
+    let results = (
+        update(&[], 15, "old", "new").unwrap(),
+        update(&vars, 15, "old", "new").unwrap(),
+        update(&vars, 20, "first\nsecond", "first\nthird").unwrap(),
+    );
+    let nested = update(&vars, 30, "old", "new").unwrap_err();
+    let updated = fs::read_to_string(root.join("tests/update.rs")).unwrap();
+    let _ = fs::remove_dir_all(&root);
+
+    assert_eq!(results, (false, true, true));
+    let message = nested.downcast::<String>().unwrap();
+    assert!(
+        message.ends_with(
+            "tests/update.rs:30:5: found \"label\", which is not the expected value. \
+             Is the assertion nested in another macro?"
+        ),
+        "{}",
+        message
+    );
+    let expected = original
+        .replace(r#"render(), "old");"#, r#"render(), "new");"#)
+        .replace("\"first\\n\\\n         second\"", "r\"first\nthird\"");
+    assert_eq!(updated, expected);
+}
+
+#[test]
+fn snapshot_mismatch() {
+    let message = failure_message(&[], || {
+        pretty_assertions::assert_snapshot!(5, "custom {} message", "panic");
+    });
+    assert_eq!(
+        message,
+        "assertion failed: `(snapshot == value)`: custom panic message
+
+Snapshot snapshots/environment__snapshot_mismatch.snap does not match.
+Set PRETTY_ASSERTIONS_UPDATE=1 to update it.
+
+\u{1b}[1mDiff\u{1b}[0m \u{1b}[31m< left\u{1b}[0m / \u{1b}[32mright >\u{1b}[0m :
+\u{1b}[31m<\u{1b}[0m\u{1b}[1;48;5;52;31m3\u{1b}[0m
+\u{1b}[32m>\u{1b}[0m\u{1b}[1;48;5;22;32m5\u{1b}[0m
+
+"
+    );
+}
+
+#[test]
+fn file_mismatch() {
+    let message = failure_message(&[], || {
+        let report = "name: web\nstatus: failed\nretries: 3\n";
+        pretty_assertions::assert_file_eq!(
+            report,
+            "tests/golden/assert_file_eq.expected",
+            "custom {} message",
+            "panic"
+        );
+    });
+    assert_eq!(
+        message,
+        "assertion failed: `(file == value)`: custom panic message
+
+File tests/golden/assert_file_eq.expected differs at line 2.
+Set PRETTY_ASSERTIONS_UPDATE=1 to update it.
+
+\u{1b}[1mDiff\u{1b}[0m \u{1b}[31m< left\u{1b}[0m / \u{1b}[32mright >\u{1b}[0m :
+ name: web
+\u{1b}[31m<status: \u{1b}[0m\u{1b}[1;48;5;52;31mok\u{1b}[0m
+\u{1b}[32m>status: \u{1b}[0m\u{1b}[1;48;5;22;32mfailed\u{1b}[0m
+ retries: 3
+ 
+
+"
+    );
+}
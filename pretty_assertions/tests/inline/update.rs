@@ -0,0 +1,31 @@
+//! A source file whose expected values are updated by `environment.rs`, in a copy.
+//!
+//! It is not built, and the line and column of each assertion are relied on.
+
+use pretty_assertions::assert_str_eq;
+
+macro_rules! labelled {
+    ($assertion:expr, $label:expr) => {
+        $assertion
+    };
+}
+
+#[test]
+fn plain() {
+    assert_str_eq!(render(), "old");
+}
+
+#[test]
+fn multiline() {
+    assert_str_eq!(
+        render(),
+        "first\n\
+         second",
+        "with a message"
+    );
+}
+
+#[test]
+fn nested() {
+    labelled!(assert_str_eq!(render(), "old"), "label");
+}
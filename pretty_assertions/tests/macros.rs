@@ -70,16 +70,38 @@ mod assert_str_eq {
     }
 
     #[test]
-    #[should_panic(expected = r#"assertion failed: `(left == right)`
+    fn passes_options() {
+        ::pretty_assertions::assert_str_eq!(
+            "SELECT id\n  FROM   users\n\n",
+            "select id\nfrom users",
+            options = [
+                ignore_case,
+                collapse_whitespace,
+                trim_lines,
+                ignore_blank_lines
+            ],
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"assertion failed: `(left == right)`: custom panic message
 
 [1mDiff[0m [31m< left[0m / [32mright >[0m :
- foo
-[31m<ba[0m[1;48;5;52;31mr[0m
-[32m>ba[0m[1;48;5;22;32mz[0m
+ SELECT id
+   FROM users
+[31m<[0m[1;48;5;52;31mWHERE[0m[31m id = [0m[1;48;5;52;31m1[0m
+[32m>[0m[1;48;5;22;32mwhere[0m[32m id = [0m[1;48;5;22;32m2[0m
 
-"#)]
-    fn fails_foo() {
-        ::pretty_assertions::assert_str_eq!("foo\nbar", "foo\nbaz");
+"#
+    )]
+    fn fails_options() {
+        ::pretty_assertions::assert_str_eq!(
+            "SELECT id\n  FROM users\nWHERE id = 1",
+            "select id\nfrom users\nwhere id = 2",
+            options = [ignore_case, trim_lines],
+            "custom panic message"
+        );
     }
 }
 
@@ -149,6 +171,28 @@ mod assert_eq {
         ::pretty_assertions::assert_eq!(*a, *b);
     }
 
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(left == right)`
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+ [
+     [0..8]: 0, 1, 2, 3, 4, 5, 6, 7,
+     [8..12]: 8, 9, 10, 11,
+[31m<    [12]: 1[0m[1;48;5;52;31m2[0m[31m,[0m
+[32m>    [12]: 1[0m[1;48;5;22;32m0[0m[32m,[0m
+     [13..20]: 13, 14, 15, 16, 17, 18, 19,
+ ]
+
+"#)]
+    fn fails_long_sequence() {
+        let a: [u32; 20] = [
+            0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19,
+        ];
+        let mut b = a;
+        b[12] = 10;
+        ::pretty_assertions::assert_eq!(a, b);
+    }
+
     #[test]
     #[should_panic(
         expected = r#"assertion failed: `(left == right)`: custom panic message
@@ -204,6 +248,217 @@ mod assert_eq {
     }
 }
 
+mod assert_eq_redacted {
+    #[derive(::core::fmt::Debug)]
+    #[allow(dead_code)]
+    struct Event {
+        id: u64,
+        name: &'static str,
+        tags: &'static [&'static str],
+    }
+
+    #[test]
+    fn passes() {
+        let a = Event {
+            id: 1,
+            name: "signup",
+            tags: &["a"],
+        };
+        let b = Event {
+            id: 2,
+            name: "signup",
+            tags: &["b", "c"],
+        };
+        ::pretty_assertions::assert_eq_redacted!(a, b, ignore = ["id", ".tags"]);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(left == right)`
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+ Event {
+     id: [redacted],
+[31m<    name: "[0m[1;48;5;52;31msi[0m[31mgn[0m[1;48;5;52;31mup[0m[31m",[0m
+[32m>    name: "[0m[1;48;5;22;32mlo[0m[32mg[0m[1;48;5;22;32mi[0m[32mn",[0m
+     tags: [
+         "a",
+     ],
+ }
+
+"#)]
+    fn fails() {
+        let a = Event {
+            id: 1,
+            name: "signup",
+            tags: &["a"],
+        };
+        let b = Event {
+            id: 2,
+            name: "login",
+            tags: &["a"],
+        };
+        ::pretty_assertions::assert_eq_redacted!(a, b, ignore = [".id"]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"assertion failed: `(left == right)`: custom panic message
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+ Event {
+[31m<    id: [0m[1;48;5;52;31m1[0m[31m,[0m
+[32m>    id: [0m[1;48;5;22;32m2[0m[32m,[0m
+     name: "signup",
+     tags: [
+         [redacted],
+     ],
+ }
+
+"#
+    )]
+    fn fails_custom() {
+        let a = Event {
+            id: 1,
+            name: "signup",
+            tags: &["a"],
+        };
+        let b = Event {
+            id: 2,
+            name: "signup",
+            tags: &["b"],
+        };
+        ::pretty_assertions::assert_eq_redacted!(
+            a,
+            b,
+            ignore = [".tags[*]"],
+            "custom panic message"
+        );
+    }
+}
+
+#[allow(clippy::eq_op)]
+mod assert_bytes_eq {
+    #[cfg(feature = "alloc")]
+    use ::alloc::vec::Vec;
+    #[cfg(feature = "std")]
+    use ::std::vec::Vec;
+
+    #[test]
+    fn passes() {
+        let a: &[u8] = b"\x00\x01foo";
+        ::pretty_assertions::assert_bytes_eq!(a, a);
+    }
+
+    #[test]
+    fn passes_comparable_types() {
+        let s0: Vec<u8> = b"\x00\x01foo".to_vec();
+        let s1: &[u8; 5] = b"\x00\x01foo";
+        ::pretty_assertions::assert_bytes_eq!(s0, s1);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(left == right)`
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+[31m<00000000: 0001 [0m[1;48;5;52;31m66[0m[31m6f 6f                             ..[0m[1;48;5;52;31mf[0m[31moo[0m
+[32m>00000000: 0001 [0m[1;48;5;22;32m62[0m[32m6f 6f                             ..[0m[1;48;5;22;32mb[0m[32moo[0m
+
+"#)]
+    fn fails() {
+        ::pretty_assertions::assert_bytes_eq!(b"\x00\x01foo", b"\x00\x01boo");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"assertion failed: `(left == right)`: custom panic message
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+"#
+    )]
+    fn fails_custom() {
+        ::pretty_assertions::assert_bytes_eq!(b"foo", b"boo", "custom panic message");
+    }
+}
+
+mod assert_iter_eq {
+    use ::core::iter::Iterator;
+
+    #[test]
+    fn passes() {
+        ::pretty_assertions::assert_iter_eq!(0..5, (0..10).take(5));
+    }
+
+    #[test]
+    fn passes_empty() {
+        ::pretty_assertions::assert_iter_eq!(0..0, 5..5);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(left == right)`
+
+First difference at index 5.
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+ {
+     2: 2,
+     3: 3,
+     4: 4,
+[31m<    5: [0m[1;48;5;52;31m5[0m[31m,[0m
+[32m>    5: [0m[1;48;5;22;32m0[0m[32m,[0m
+     6: 6,
+     7: 7,
+     8: 8,
+ }
+
+"#)]
+    fn fails() {
+        let b = (0..10).map(|x| if x == 5 { 0 } else { x });
+        ::pretty_assertions::assert_iter_eq!(0..10, b);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"assertion failed: `(left == right)`: custom panic message
+
+First difference at index 2. Left has 2 elements, right has 7.
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+ {
+     0: 0,
+     1: 1,
+[32m>    2: 2,[0m
+[32m>    3: 3,[0m
+[32m>    4: 4,[0m
+[32m>    5: 5,[0m
+ }
+
+"#
+    )]
+    fn fails_length_custom() {
+        ::pretty_assertions::assert_iter_eq!(0..2, 0..7, "custom panic message");
+    }
+
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(left == right)`
+
+First difference at index 2. Left has 2 elements, right has at least 1007.
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+ {
+     0: 0,
+     1: 1,
+[32m>    2: 2,[0m
+[32m>    3: 3,[0m
+[32m>    4: 4,[0m
+[32m>    5: 5,[0m
+ }
+
+"#)]
+    fn fails_unbounded() {
+        ::pretty_assertions::assert_iter_eq!(0..2, 0..);
+    }
+}
+
 mod assert_ne {
     #[cfg(feature = "alloc")]
     use ::alloc::string::{String, ToString};
@@ -305,80 +560,173 @@ Both sides:
     }
 }
 
-#[cfg(feature = "unstable")]
-mod assert_matches {
-    use ::core::option::Option::{None, Some};
+mod assert_ok {
+    use ::core::result::Result::{self, Err, Ok};
+
+    #[cfg(feature = "alloc")]
+    use ::alloc::string::{String, ToString};
+    #[cfg(feature = "std")]
+    use ::std::string::{String, ToString};
 
     #[test]
     fn passes() {
-        let a = Some("some value");
-        ::pretty_assertions::assert_matches!(a, Some(_));
+        let a: Result<u32, u32> = Ok(3);
+        ::pretty_assertions::assert_ok!(a);
     }
 
     #[test]
-    fn passes_unsized() {
-        let a: &[u8] = b"e";
-        ::pretty_assertions::assert_matches!(*a, _);
+    fn passes_value() {
+        let a: Result<String, u32> = Ok("foo".to_string());
+        ::pretty_assertions::assert_ok!(a, "foo");
     }
 
     #[test]
-    #[should_panic(expected = r#"assertion failed: `(left matches right)`
+    #[should_panic(expected = r#"assertion failed: `(left is Ok(_))`
 
-[1mDiff[0m [31m< left[0m / [32mright >[0m :
-[31m<[0m[1;48;5;52;31mN[0m[31mo[0m[1;48;5;52;31mn[0m[31me[0m
-[32m>[0m[1;48;5;22;32mS[0m[32mo[0m[1;48;5;22;32mm[0m[32me[0m[1;48;5;22;32m(_)[0m
+Expected Ok, found Err:
+"oh no"
 
 "#)]
     fn fails() {
-        ::pretty_assertions::assert_matches!(None::<usize>, Some(_));
+        let a: Result<u32, &str> = Err("oh no");
+        ::pretty_assertions::assert_ok!(a);
     }
 
     #[test]
-    #[should_panic(expected = r#"assertion failed: `(left matches right)`
+    #[should_panic(expected = r#"assertion failed: `(left == Ok(right))`
+
+Expected Ok, found Err:
+"oh no"
+
+"#)]
+    fn fails_value_variant() {
+        let a: Result<u32, &str> = Err("oh no");
+        ::pretty_assertions::assert_ok!(a, 666);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(left == Ok(right))`
 
 [1mDiff[0m [31m< left[0m / [32mright >[0m :
-[31m<Some([0m
-[31m<    3,[0m
-[31m<)[0m
-[32m>Some(3) if 0 > 0[0m
+[31m<[0m[1;48;5;52;31m666[0m
+[32m>[0m[1;48;5;22;32m999[0m
 
 "#)]
-    fn fails_guard() {
-        ::pretty_assertions::assert_matches!(Some(3), Some(3) if 0 > 0,);
+    fn fails_value() {
+        let a: Result<u32, &str> = Ok(666);
+        ::pretty_assertions::assert_ok!(a, 999);
     }
 
     #[test]
-    #[should_panic(expected = r#"assertion failed: `(left matches right)`
+    #[should_panic(
+        expected = r#"assertion failed: `(left == Ok(right))`: custom panic message
 
 [1mDiff[0m [31m< left[0m / [32mright >[0m :
-[31m<[[0m
-[31m<    101,[0m
-[31m<][0m
-[32m>ref b if b == b"ee"[0m
+ foo
+[31m<ba[0m[1;48;5;52;31mr[0m
+[32m>ba[0m[1;48;5;22;32mz[0m
+
+"#
+    )]
+    fn fails_value_str_custom() {
+        let a: Result<&str, u32> = Ok("foo\nbar");
+        ::pretty_assertions::assert_ok!(a, "foo\nbaz", "custom panic message");
+    }
+}
+
+mod assert_err {
+    use ::core::result::Result::{self, Err, Ok};
+
+    #[test]
+    fn passes() {
+        let a: Result<u32, u32> = Err(3);
+        ::pretty_assertions::assert_err!(a);
+    }
+
+    #[test]
+    fn passes_value() {
+        let a: Result<u32, &str> = Err("oh no");
+        ::pretty_assertions::assert_err!(a, "oh no");
+    }
+
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(left is Err(_))`
+
+Expected Err, found Ok:
+666
 
 "#)]
-    fn fails_unsized() {
-        let a: &[u8] = b"e";
-        ::pretty_assertions::assert_matches!(*a, ref b if b == b"ee");
+    fn fails() {
+        let a: Result<u32, u32> = Ok(666);
+        ::pretty_assertions::assert_err!(a);
     }
 
     #[test]
     #[should_panic(
-        expected = r#"assertion failed: `(left matches right)`: custom panic message
+        expected = r#"assertion failed: `(left == Err(right))`: custom panic message
+
+Expected Err, found Ok:
+666
+
+"#
+    )]
+    fn fails_value_variant_custom() {
+        let a: Result<u32, u32> = Ok(666);
+        ::pretty_assertions::assert_err!(a, 666, "custom panic message");
+    }
+
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(left == Err(right))`
 
 [1mDiff[0m [31m< left[0m / [32mright >[0m :
 [31m<[0m[1;48;5;52;31m666[0m
 [32m>[0m[1;48;5;22;32m999[0m
 
-"#
-    )]
-    fn fails_custom() {
-        ::pretty_assertions::assert_matches!(666, 999, "custom panic message");
+"#)]
+    fn fails_value() {
+        let a: Result<u32, u32> = Err(666);
+        ::pretty_assertions::assert_err!(a, 999);
+    }
+}
+
+mod assert_some {
+    use ::core::option::Option::{self, None, Some};
+
+    #[test]
+    fn passes() {
+        ::pretty_assertions::assert_some!(Some(3));
+    }
+
+    #[test]
+    fn passes_value() {
+        ::pretty_assertions::assert_some!(Some(3), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(left is Some(_))`
+
+Expected Some, found None
+
+"#)]
+    fn fails() {
+        let a: Option<u32> = None;
+        ::pretty_assertions::assert_some!(a);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(left == Some(right))`
+
+Expected Some, found None
+
+"#)]
+    fn fails_value_variant() {
+        let a: Option<u32> = None;
+        ::pretty_assertions::assert_some!(a, 666);
     }
 
     #[test]
     #[should_panic(
-        expected = r#"assertion failed: `(left matches right)`: custom panic message
+        expected = r#"assertion failed: `(left == Some(right))`: custom panic message
 
 [1mDiff[0m [31m< left[0m / [32mright >[0m :
 [31m<[0m[1;48;5;52;31m666[0m
@@ -386,7 +734,823 @@ mod assert_matches {
 
 "#
     )]
-    fn fails_custom_trailing_comma() {
-        ::pretty_assertions::assert_matches!(666, 999, "custom panic message",);
+    fn fails_value_custom() {
+        ::pretty_assertions::assert_some!(Some(666), 999, "custom panic message");
+    }
+}
+
+mod assert_none {
+    use ::core::option::Option::{self, None, Some};
+
+    #[test]
+    fn passes() {
+        let a: Option<u32> = None;
+        ::pretty_assertions::assert_none!(a);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(left is None)`
+
+Expected None, found Some:
+666
+
+"#)]
+    fn fails() {
+        ::pretty_assertions::assert_none!(Some(666));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(left is None)`: custom panic message
+
+Expected None, found Some:
+666
+
+"#)]
+    fn fails_custom() {
+        ::pretty_assertions::assert_none!(Some(666), "custom panic message");
+    }
+}
+
+mod check_eq {
+    use ::core::option::Option::Some;
+    use ::core::result::Result;
+
+    #[cfg(feature = "alloc")]
+    use ::alloc::string::ToString;
+    #[cfg(feature = "std")]
+    use ::std::string::ToString;
+
+    #[test]
+    fn passes() -> Result<(), ::pretty_assertions::Mismatch> {
+        ::pretty_assertions::check_eq!(666, 666)?;
+        ::pretty_assertions::check_eq!("foo", "foo".to_string(),)
+    }
+
+    #[test]
+    fn fails() {
+        let mismatch = ::pretty_assertions::check_eq!(666, 999).unwrap_err();
+        ::pretty_assertions::assert_eq!(mismatch.left(), "666");
+        ::pretty_assertions::assert_eq!(mismatch.right(), "999");
+        ::pretty_assertions::assert_none!(mismatch.message());
+    }
+
+    #[test]
+    fn fails_custom() {
+        let mismatch =
+            ::pretty_assertions::check_eq!(666, 999, "custom {} message", "panic").unwrap_err();
+        ::pretty_assertions::assert_eq!(mismatch.message(), Some("custom panic message"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    #[should_panic(expected = r#"check failed: `(left == right)`: custom panic message
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+[31m<[0m[1;48;5;52;31m666[0m
+[32m>[0m[1;48;5;22;32m999[0m
+"#)]
+    fn fails_unwrap() {
+        fn check() -> Result<(), ::std::boxed::Box<dyn ::std::error::Error>> {
+            ::pretty_assertions::check_eq!(666, 999, "custom panic message")?;
+            ::core::result::Result::Ok(())
+        }
+        check().unwrap();
+    }
+}
+
+mod soft_assert_eq {
+    #[test]
+    fn passes() {
+        let mut soft = ::pretty_assertions::SoftAssertions::new();
+        ::core::assert!(::pretty_assertions::soft_assert_eq!(soft, 666, 666));
+        ::pretty_assertions::soft_assert_eq!(soft, "foo", "foo", "custom panic message");
+        ::core::assert!(soft.is_empty());
+        soft.finish();
+    }
+
+    #[test]
+    #[should_panic(expected = r#"1 soft assertion failed:
+
+1) "#)]
+    fn fails_finish() {
+        let mut soft = ::pretty_assertions::SoftAssertions::new();
+        ::pretty_assertions::soft_assert_eq!(soft, 666, 999);
+        soft.finish();
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn fails() {
+        let line = ::core::line!();
+        let payload = ::std::panic::catch_unwind(|| {
+            let mut soft = ::pretty_assertions::SoftAssertions::new();
+            ::pretty_assertions::soft_assert_eq!(soft, 666, 999);
+            ::pretty_assertions::soft_assert_eq!(soft, 666, 666);
+            ::pretty_assertions::soft_assert_eq!(soft, "foo", "bar", "custom {} message", "panic");
+            ::pretty_assertions::assert_eq!(soft.len(), 2);
+        })
+        .unwrap_err();
+        let message = payload.downcast::<::std::string::String>().unwrap();
+        ::pretty_assertions::assert_str_eq!(
+            *message,
+            ::std::format!(
+                "2 soft assertions failed:
+
+1) {file}:{first}:13
+check failed: `(left == right)`
+
+\u{1b}[1mDiff\u{1b}[0m \u{1b}[31m< left\u{1b}[0m / \u{1b}[32mright >\u{1b}[0m :
+\u{1b}[31m<\u{1b}[0m\u{1b}[1;48;5;52;31m666\u{1b}[0m
+\u{1b}[32m>\u{1b}[0m\u{1b}[1;48;5;22;32m999\u{1b}[0m
+
+2) {file}:{second}:13
+check failed: `(left == right)`: custom panic message
+
+\u{1b}[1mDiff\u{1b}[0m \u{1b}[31m< left\u{1b}[0m / \u{1b}[32mright >\u{1b}[0m :
+\u{1b}[31m<\"\u{1b}[0m\u{1b}[1;48;5;52;31mfoo\u{1b}[0m\u{1b}[31m\"\u{1b}[0m
+\u{1b}[32m>\"\u{1b}[0m\u{1b}[1;48;5;22;32mbar\u{1b}[0m\u{1b}[32m\"\u{1b}[0m
+",
+                file = ::core::file!(),
+                first = line + 3,
+                second = line + 5,
+            )
+        );
+    }
+}
+
+mod debug_assert {
+    use ::core::option::Option::{None, Some};
+    use ::core::result::Result::{self, Ok};
+
+    #[test]
+    fn passes() {
+        let a: Result<u32, u32> = Ok(3);
+        ::pretty_assertions::debug_assert_eq!(3, 3);
+        ::pretty_assertions::debug_assert_str_eq!("foo", "foo");
+        ::pretty_assertions::debug_assert_ne!(3, 4);
+        ::pretty_assertions::debug_assert_ok!(a, 3);
+        ::pretty_assertions::debug_assert_some!(Some(3));
+        ::pretty_assertions::debug_assert_none!(None::<u32>);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(
+        expected = r#"assertion failed: `(left == right)`: custom panic message
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+[31m<[0m[1;48;5;52;31m666[0m
+[32m>[0m[1;48;5;22;32m999[0m
+
+"#
+    )]
+    fn fails_custom() {
+        ::pretty_assertions::debug_assert_eq!(666, 999, "custom panic message");
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(left is Err(_))`"#)]
+    fn fails_err() {
+        let a: Result<u32, u32> = Ok(666);
+        ::pretty_assertions::debug_assert_err!(a);
+    }
+
+    #[cfg(not(debug_assertions))]
+    #[test]
+    fn skipped() {
+        ::pretty_assertions::debug_assert_eq!(666, 999);
+        ::pretty_assertions::debug_assert_ne!(666, 666);
+    }
+}
+
+#[cfg(feature = "unstable")]
+mod assert_matches {
+    use ::core::option::Option::{None, Some};
+
+    #[test]
+    fn passes() {
+        let a = Some("some value");
+        ::pretty_assertions::assert_matches!(a, Some(_));
+    }
+
+    #[test]
+    fn passes_unsized() {
+        let a: &[u8] = b"e";
+        ::pretty_assertions::assert_matches!(*a, _);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(left matches right)`
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+[31m<[0m[1;48;5;52;31mN[0m[31mo[0m[1;48;5;52;31mn[0m[31me[0m
+[32m>[0m[1;48;5;22;32mS[0m[32mo[0m[1;48;5;22;32mm[0m[32me[0m[1;48;5;22;32m(_)[0m
+
+"#)]
+    fn fails() {
+        ::pretty_assertions::assert_matches!(None::<usize>, Some(_));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(left matches right)`
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+[31m<Some([0m
+[31m<    3,[0m
+[31m<)[0m
+[32m>Some(3) if 0 > 0[0m
+
+"#)]
+    fn fails_guard() {
+        ::pretty_assertions::assert_matches!(Some(3), Some(3) if 0 > 0,);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(left matches right)`
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+[31m<[[0m
+[31m<    101,[0m
+[31m<][0m
+[32m>ref b if b == b"ee"[0m
+
+"#)]
+    fn fails_unsized() {
+        let a: &[u8] = b"e";
+        ::pretty_assertions::assert_matches!(*a, ref b if b == b"ee");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"assertion failed: `(left matches right)`: custom panic message
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+[31m<[0m[1;48;5;52;31m666[0m
+[32m>[0m[1;48;5;22;32m999[0m
+
+"#
+    )]
+    fn fails_custom() {
+        ::pretty_assertions::assert_matches!(666, 999, "custom panic message");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"assertion failed: `(left matches right)`: custom panic message
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+[31m<[0m[1;48;5;52;31m666[0m
+[32m>[0m[1;48;5;22;32m999[0m
+
+"#
+    )]
+    fn fails_custom_trailing_comma() {
+        ::pretty_assertions::assert_matches!(666, 999, "custom panic message",);
+    }
+}
+
+#[cfg(feature = "serde_json")]
+mod assert_json_eq {
+    use ::core::convert::Into;
+
+    #[test]
+    fn passes() {
+        let a = ::serde_json::json!({"b": [1, 2], "a": null});
+        let b = ::serde_json::json!({"a": null, "b": [1, 2]});
+        ::pretty_assertions::assert_json_eq!(a, b);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(left == right)`
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+ {
+[31m<  "age": 4[0m[1;48;5;52;31m2[0m[31m,[0m
+[32m>  "age": 4[0m[1;48;5;22;32m3[0m[32m,[0m
+   "name": "Alice"
+ }
+
+"#)]
+    fn fails() {
+        let a = ::serde_json::json!({"name": "Alice", "age": 42});
+        let b = ::serde_json::json!({"age": 43, "name": "Alice"});
+        ::pretty_assertions::assert_json_eq!(a, b);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"assertion failed: `(left == right)`: custom panic message
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+[31m<1[0m
+[32m>[0m[1;48;5;22;32m"[0m[32m1[0m[1;48;5;22;32m"[0m
+
+"#
+    )]
+    fn fails_custom() {
+        let a = ::serde_json::json!(1);
+        let b = ::serde_json::json!("1");
+        ::pretty_assertions::assert_json_eq!(a, b, "custom panic message");
+    }
+}
+
+#[cfg(feature = "serde_json")]
+mod assert_json_include {
+    use ::core::convert::Into;
+
+    #[test]
+    fn passes() {
+        let a = ::serde_json::json!({"id": 1, "roles": [{"id": 2, "name": "admin"}]});
+        let b = ::serde_json::json!({"roles": [{"name": "admin"}]});
+        ::pretty_assertions::assert_json_include!(a, b);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(left includes right)`
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+ {
+[31m<  "name": "Al[0m[1;48;5;52;31mic[0m[31me"[0m
+[32m>  "name": "Al[0m[1;48;5;22;32mbert[0m[32me"[0m
+ }
+
+"#)]
+    fn fails() {
+        let a = ::serde_json::json!({"id": 1, "name": "Alice"});
+        let b = ::serde_json::json!({"name": "Alberte"});
+        ::pretty_assertions::assert_json_include!(a, b);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(left includes right)`
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+[31m<{}[0m
+[32m>{[0m
+[32m>  "id": 1[0m
+[32m>}[0m
+
+"#)]
+    fn fails_missing_field() {
+        let a = ::serde_json::json!({});
+        let b = ::serde_json::json!({"id": 1});
+        ::pretty_assertions::assert_json_include!(a, b);
+    }
+}
+
+#[cfg(feature = "regex")]
+mod assert_matches_regex {
+    #[cfg(feature = "alloc")]
+    use ::alloc::string::{String, ToString};
+    #[cfg(feature = "std")]
+    use ::std::string::{String, ToString};
+
+    #[test]
+    fn passes() {
+        ::pretty_assertions::assert_matches_regex!("foo=1", r"^\w+=\d+$");
+    }
+
+    #[test]
+    fn passes_multi_line() {
+        ::pretty_assertions::assert_matches_regex!("foo=1\nbar=2\n", r"(?m)^bar=\d+$");
+    }
+
+    #[test]
+    fn passes_string() {
+        let text: String = "foo=1".to_string();
+        let pattern: String = r"^\w+=\d+$".to_string();
+        ::pretty_assertions::assert_matches_regex!(text, pattern);
+    }
+
+    #[test]
+    fn passes_regex() {
+        let pattern = ::regex::Regex::new(r"^\w+=\d+$").unwrap();
+        ::pretty_assertions::assert_matches_regex!("foo=1", pattern);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(text matches pattern)`
+
+Pattern: bar
+
+Text:
+foo
+baz
+
+"#)]
+    fn fails() {
+        ::pretty_assertions::assert_matches_regex!("foo\nbaz", "bar");
+    }
+
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(text matches pattern)`
+
+Pattern: ^\w+=\d+$
+
+[1mLines[0m [31m< unmatched[0m / matched :
+ foo=1
+[31m<bar=x[0m
+ baz=3
+
+"#)]
+    fn fails_line_anchored() {
+        ::pretty_assertions::assert_matches_regex!("foo=1\nbar=x\nbaz=3", r"^\w+=\d+$");
+    }
+
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(text matches pattern)`
+
+Pattern: ^\d+$
+
+Text:
+
+
+"#)]
+    fn fails_empty() {
+        ::pretty_assertions::assert_matches_regex!("", r"^\d+$");
+    }
+
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(text matches pattern)`
+
+Pattern: ^.*$
+
+Text:
+foo
+bar
+
+"#)]
+    fn fails_every_line_matches() {
+        ::pretty_assertions::assert_matches_regex!("foo\nbar", r"^.*$");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"assertion failed: `(text matches pattern)`: custom panic message
+
+Pattern: bar
+"#
+    )]
+    fn fails_custom() {
+        ::pretty_assertions::assert_matches_regex!("foo", "bar", "custom panic message");
+    }
+
+    #[test]
+    #[should_panic(expected = r#"assertion failed: invalid pattern"#)]
+    fn fails_invalid_pattern() {
+        ::pretty_assertions::assert_matches_regex!("foo", "(");
+    }
+}
+
+#[cfg(feature = "serde")]
+mod assert_serde_eq {
+    #[derive(::core::cmp::PartialEq, ::serde::Serialize)]
+    struct Foo {
+        lorem: &'static str,
+        ipsum: u32,
+        dolor: ::core::option::Option<char>,
+    }
+
+    #[test]
+    fn passes() {
+        let a = Foo {
+            lorem: "Hello World!",
+            ipsum: 42,
+            dolor: ::core::option::Option::None,
+        };
+        ::pretty_assertions::assert_serde_eq!(a, a);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(left == right)`
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+ Foo {
+[31m<    lorem: "Hello W[0m[1;48;5;52;31mo[0m[31mrld!",[0m
+[32m>    lorem: "Hello Wr[0m[1;48;5;22;32mo[0m[32mld!",[0m
+     ipsum: 42,
+[31m<    dolor: None,[0m
+[32m>    dolor: Some([0m
+[32m>        'x',[0m
+[32m>    ),[0m
+ }
+
+"#)]
+    fn fails() {
+        let a = Foo {
+            lorem: "Hello World!",
+            ipsum: 42,
+            dolor: ::core::option::Option::None,
+        };
+        let b = Foo {
+            lorem: "Hello Wrold!",
+            ipsum: 42,
+            dolor: ::core::option::Option::Some('x'),
+        };
+        ::pretty_assertions::assert_serde_eq!(a, b);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"assertion failed: `(left == right)`: custom panic message
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+[31m<[0m[1;48;5;52;31m1[0m
+[32m>[0m[1;48;5;22;32m2[0m
+
+"#
+    )]
+    fn fails_custom() {
+        ::pretty_assertions::assert_serde_eq!(1, 2, "custom panic message");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(left == right)`
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+ [
+[31m<    [0]: 0,[0m
+     [1..9 / 0..8]: 1, 2, 3, 4, 5, 6, 7, 8,
+     [9..12 / 8..11]: 9, 10, 11,
+[32m>    [11]: 12,[0m
+ ]
+
+"#)]
+    fn fails_sort_sequences() {
+        let a: ::std::collections::HashSet<u32> = ::core::iter::Iterator::collect(0..12);
+        let b: ::std::collections::HashSet<u32> = ::core::iter::Iterator::collect(1..13);
+        ::pretty_assertions::assert_serde_eq!(a, b, options = [sort_sequences]);
+    }
+}
+
+#[cfg(feature = "serde_yaml")]
+mod assert_yaml_eq {
+    #[test]
+    fn passes() {
+        let a = "name: web\nlabels: {app: web}\nports: [80, 443]\n";
+        let b = "ports:\n  - 80\n  - 443\nlabels:\n  app: 'web'\nname: \"web\"\n";
+        ::pretty_assertions::assert_yaml_eq!(a, b);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(left == right)`
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+metadata.labels:
+[31m<{[0m
+[31m<    tier: "frontend",[0m
+[31m<}[0m
+spec.paused:
+[32m>true[0m
+spec.replicas:
+[31m<[0m[1;48;5;52;31m3[0m
+[32m>[0m[1;48;5;22;32m4[0m
+
+"#)]
+    fn fails() {
+        let a = "metadata:\n  name: web\n  labels:\n    tier: frontend\nspec:\n  replicas: 3\n";
+        let b = "spec:\n  replicas: 4\n  paused: true\nmetadata:\n  name: web\n";
+        ::pretty_assertions::assert_yaml_eq!(a, b);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(left == right)`
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+(document 2).spec.replicas:
+[31m<[0m[1;48;5;52;31m3[0m
+[32m>[0m[1;48;5;22;32m4[0m
+(document 3):
+[32m>{[0m
+[32m>    kind: "Job",[0m
+[32m>}[0m
+
+"#)]
+    fn fails_documents() {
+        let a = "kind: Service\n---\nkind: Deployment\nspec:\n  replicas: 3\n";
+        let b = "kind: Service\n---\nkind: Deployment\nspec:\n  replicas: 4\n---\nkind: Job\n";
+        ::pretty_assertions::assert_yaml_eq!(a, b);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"assertion failed: `(left == right)`: custom panic message
+
+Failed to parse left as YAML: did not find expected ',' or ']' at line 2 column 1, while parsing a flow sequence at line 1 column 4
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+[31m<a: [1[0m
+[32m>a: [1[0m[1;48;5;22;32m][0m
+
+"#
+    )]
+    fn fails_invalid() {
+        ::pretty_assertions::assert_yaml_eq!("a: [1", "a: [1]", "custom panic message");
+    }
+}
+
+#[cfg(feature = "toml")]
+mod assert_toml_eq {
+    #[test]
+    fn passes() {
+        let a = "[package]\nname = \"foo\"\n\n[dependencies]\nserde = \"1\"\n";
+        let b = "dependencies = { serde = '1' }\npackage = { name = 'foo' }\n";
+        ::pretty_assertions::assert_toml_eq!(a, b);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(left == right)`
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+package.authors[1]:
+[31m<"[0m[1;48;5;52;31mb[0m[31m"[0m
+[32m>"[0m[1;48;5;22;32mc[0m[32m"[0m
+
+"#)]
+    fn fails() {
+        let a = "[package]\nname = \"foo\"\nauthors = [\"a\", \"b\"]\n";
+        let b = "[package]\nname = \"foo\"\nauthors = [\"a\", \"c\"]\n";
+        ::pretty_assertions::assert_toml_eq!(a, b);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"assertion failed: `(left == right)`: custom panic message
+
+Failed to parse left as TOML: TOML parse error at line 1, column 5"#
+    )]
+    fn fails_invalid() {
+        ::pretty_assertions::assert_toml_eq!("a = ", "a = 1", "custom panic message");
+    }
+}
+
+#[cfg(feature = "tl")]
+mod assert_html_eq {
+    #[test]
+    fn passes() {
+        let a = r#"<div class="card" id="a"><p>Hello   world</p><br></div>"#;
+        let b = "<div id='a' class='card'>\n  <p>\n    Hello world\n  </p>\n  <br/>\n</div>";
+        ::pretty_assertions::assert_html_eq!(a, b);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(left == right)`
+
+Mismatched elements:
+  /ul: attributes differ
+  /ul/li[2]/a: attributes differ
+  /ul/li[2]/a/text(): text differs
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+[31m<<ul class="[0m[1;48;5;52;31mme[0m[31mn[0m[1;48;5;52;31mu[0m[31m">[0m
+[32m><ul class="n[0m[1;48;5;22;32mav[0m[32m">[0m
+   <li>Home</li>
+   <li>
+[31m<    <a href="/[0m[1;48;5;52;31mab[0m[31mo[0m[1;48;5;52;31mu[0m[31mt">[0m[1;48;5;52;31mAb[0m[31mo[0m[1;48;5;52;31mu[0m[31mt</a>[0m
+[32m>    <a href="/[0m[1;48;5;22;32mc[0m[32mo[0m[1;48;5;22;32mn[0m[32mt[0m[1;48;5;22;32mact[0m[32m">[0m[1;48;5;22;32mC[0m[32mo[0m[1;48;5;22;32mntac[0m[32mt</a>[0m
+   </li>
+ </ul>
+
+"#)]
+    fn fails() {
+        let a = r#"<ul class="menu"><li>Home</li><li><a href="/about">About</a></li></ul>"#;
+        let b = r#"<ul class="nav"><li>Home</li><li><a href="/contact">Contact</a></li></ul>"#;
+        ::pretty_assertions::assert_html_eq!(a, b);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"assertion failed: `(left == right)`: custom panic message
+
+Mismatched elements:
+  /p/text(): text differs
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+[31m<<p>[0m[1;48;5;52;31ma[0m[31m</p>[0m
+[32m><p>[0m[1;48;5;22;32mb[0m[32m</p>[0m
+
+"#
+    )]
+    fn fails_custom() {
+        ::pretty_assertions::assert_html_eq!("<p>a</p>", "<p>b</p>", "custom panic message");
+    }
+}
+
+#[cfg(feature = "roxmltree")]
+mod assert_xml_eq {
+    #[test]
+    fn passes() {
+        let a = r#"<feed><entry id="1" lang="en"><title>Hello</title></entry></feed>"#;
+        let b =
+            "<feed>\n  <entry lang='en' id='1'>\n    <title> Hello </title>\n  </entry>\n</feed>";
+        ::pretty_assertions::assert_xml_eq!(a, b);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(left == right)`
+
+Mismatched elements:
+  /feed/entry[2]/title/text(): text differs
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+ <feed>
+   <entry>
+     <title>One</title>
+   </entry>
+   <entry>
+[31m<    <title>T[0m[1;48;5;52;31mwo[0m[31m</title>[0m
+[32m>    <title>T[0m[1;48;5;22;32mhree[0m[32m</title>[0m
+   </entry>
+ </feed>
+
+"#)]
+    fn fails() {
+        let a = "<feed><entry><title>One</title></entry><entry><title>Two</title></entry></feed>";
+        let b = "<feed><entry><title>One</title></entry><entry><title>Three</title></entry></feed>";
+        ::pretty_assertions::assert_xml_eq!(a, b);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"assertion failed: `(left == right)`: custom panic message
+
+Failed to parse left as XML: the root node was opened but never closed
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+[31m<<a>[0m
+[32m><a[0m[1;48;5;22;32m/[0m[32m>[0m
+
+"#
+    )]
+    fn fails_invalid() {
+        ::pretty_assertions::assert_xml_eq!("<a>", "<a/>", "custom panic message");
+    }
+}
+
+#[cfg(all(feature = "regex", feature = "std"))]
+mod normalize {
+    #[test]
+    #[should_panic(expected = r#"assertion failed: `(left == right)`
+
+[1mDiff[0m [31m< left[0m / [32mright >[0m :
+ [
+     "normalize-test-N",
+[31m<    "[0m[1;48;5;52;31ma[0m[31m",[0m
+[32m>    "[0m[1;48;5;22;32mb[0m[32m",[0m
+ ]
+
+"#)]
+    fn global_normalizer() {
+        // Tests run in parallel, so only match text which no other test uses
+        ::pretty_assertions::add_global_normalizer(::pretty_assertions::Normalizer::new(
+            ::regex::Regex::new("normalize-test-[0-9]+").unwrap(),
+            "normalize-test-N",
+        ));
+        ::pretty_assertions::assert_eq!(["normalize-test-1", "a"], ["normalize-test-2", "b"]);
+    }
+}
+
+#[cfg(feature = "std")]
+mod assert_snapshot {
+    #[derive(::core::fmt::Debug)]
+    #[allow(dead_code)]
+    struct Config {
+        name: &'static str,
+        retries: u32,
+    }
+
+    #[test]
+    fn passes_debug() {
+        ::pretty_assertions::assert_snapshot!(Config {
+            name: "web",
+            retries: 3,
+        });
+    }
+
+    #[test]
+    fn passes_str() {
+        ::pretty_assertions::assert_snapshot!("first line\nsecond line");
+    }
+
+    #[test]
+    fn passes_numbered() {
+        ::pretty_assertions::assert_snapshot!("first");
+        ::pretty_assertions::assert_snapshot!("second");
+    }
+}
+
+#[cfg(feature = "std")]
+mod assert_file_eq {
+    use ::std::string::String;
+
+    fn report(status: &str) -> String {
+        ::std::format!("name: web\nstatus: {}\nretries: 3\n", status)
+    }
+
+    #[test]
+    fn passes() {
+        ::pretty_assertions::assert_file_eq!(report("ok"), "tests/golden/assert_file_eq.expected");
     }
 }